@@ -0,0 +1,545 @@
+use crate::attr::{
+    new_attr, new_nested_attr, Attrs, Nl80211AclPolicy, Nl80211Attr, Nl80211AuthType,
+    Nl80211HiddenSsid, Nl80211SmpsMode, Nl80211WpaVersions,
+};
+use crate::channel::ChannelDef;
+
+use neli::attr::Attribute;
+use neli::err::{DeError, SerError};
+use neli::genl::Nlattr;
+use neli::types::{Buffer, GenlBuffer};
+
+/// Beacon and probe response templates of an AP
+///
+/// The beacon is split around the TIM element, which is inserted by the kernel or the device:
+/// the head holds the header, the fixed fields and the elements preceding the TIM, the tail holds
+/// the elements following it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BeaconData {
+    head: Vec<u8>,
+    tail: Vec<u8>,
+    probe_resp: Option<Vec<u8>>,
+    ie: Option<Vec<u8>>,
+    ie_probe_resp: Option<Vec<u8>>,
+    ie_assoc_resp: Option<Vec<u8>>,
+}
+
+impl BeaconData {
+    /// Beacon template made of `head` and `tail`
+    pub fn new(head: &[u8], tail: &[u8]) -> Self {
+        Self {
+            head: head.to_vec(),
+            tail: tail.to_vec(),
+            probe_resp: None,
+            ie: None,
+            ie_probe_resp: None,
+            ie_assoc_resp: None,
+        }
+    }
+
+    /// Head of the beacon, up to the TIM element
+    pub fn head(&self) -> &[u8] {
+        &self.head
+    }
+
+    /// Tail of the beacon, after the TIM element
+    pub fn tail(&self) -> &[u8] {
+        &self.tail
+    }
+
+    /// Probe response template, for devices answering probe requests on their own
+    pub fn probe_resp(mut self, probe_resp: &[u8]) -> Self {
+        self.probe_resp = Some(probe_resp.to_vec());
+        self
+    }
+
+    /// Extra elements for the frames generated by the device (e.g. WPS)
+    pub fn ie(mut self, ie: &[u8]) -> Self {
+        self.ie = Some(ie.to_vec());
+        self
+    }
+
+    /// Extra elements for the probe responses generated by the device
+    pub fn ie_probe_resp(mut self, ie: &[u8]) -> Self {
+        self.ie_probe_resp = Some(ie.to_vec());
+        self
+    }
+
+    /// Extra elements for the (re)association responses generated by the device
+    pub fn ie_assoc_resp(mut self, ie: &[u8]) -> Self {
+        self.ie_assoc_resp = Some(ie.to_vec());
+        self
+    }
+
+    pub(crate) fn attrs(&self) -> Result<Vec<Nlattr<Nl80211Attr, Buffer>>, SerError> {
+        let mut attrs = vec![
+            new_attr(Nl80211Attr::AttrBeaconHead, self.head.as_slice())?,
+            new_attr(Nl80211Attr::AttrBeaconTail, self.tail.as_slice())?,
+        ];
+        if let Some(probe_resp) = &self.probe_resp {
+            attrs.push(new_attr(Nl80211Attr::AttrProbeResp, probe_resp.as_slice())?);
+        }
+        if let Some(ie) = &self.ie {
+            attrs.push(new_attr(Nl80211Attr::AttrIe, ie.as_slice())?);
+        }
+        if let Some(ie) = &self.ie_probe_resp {
+            attrs.push(new_attr(Nl80211Attr::AttrIeProbeResp, ie.as_slice())?);
+        }
+        if let Some(ie) = &self.ie_assoc_resp {
+            attrs.push(new_attr(Nl80211Attr::AttrIeAssocResp, ie.as_slice())?);
+        }
+        Ok(attrs)
+    }
+
+    pub(crate) fn append_attrs(
+        &self,
+        attrs: &mut GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), SerError> {
+        for attr in self.attrs()? {
+            attrs.push(attr);
+        }
+        Ok(())
+    }
+}
+
+/// A list of MAC addresses allowed or denied to connect to an AP
+///
+/// # Example
+///
+/// ```
+/// # use neli_wifi::{MacAcl, Nl80211AclPolicy};
+/// let acl = MacAcl::new(Nl80211AclPolicy::AclPolicyAcceptUnlessListed)
+///     .mac(&[0x02, 0, 0, 0, 1, 0])
+///     .mac(&[0x02, 0, 0, 0, 2, 0]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacAcl {
+    policy: Nl80211AclPolicy,
+    macs: Vec<Vec<u8>>,
+}
+
+impl MacAcl {
+    /// Empty list with the given policy
+    pub fn new(policy: Nl80211AclPolicy) -> Self {
+        Self {
+            policy,
+            macs: Vec::new(),
+        }
+    }
+
+    /// Add a MAC address to the list
+    pub fn mac(mut self, mac: &[u8]) -> Self {
+        self.macs.push(mac.to_vec());
+        self
+    }
+
+    pub(crate) fn append_attrs(
+        &self,
+        attrs: &mut GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), SerError> {
+        attrs.push(new_attr(
+            Nl80211Attr::AttrAclPolicy,
+            u32::from(u16::from(self.policy)),
+        )?);
+        let macs = self
+            .macs
+            .iter()
+            .zip(1u16..)
+            .map(|(mac, index)| new_attr(index, mac.as_slice()))
+            .collect::<Result<Vec<_>, _>>()?;
+        attrs.push(new_nested_attr(Nl80211Attr::AttrMacAddrs, &macs)?);
+        Ok(())
+    }
+}
+
+/// Settings used to start an AP
+///
+/// Cipher and AKM suites are given as selectors, like in [`ConnectParams`](crate::ConnectParams).
+///
+/// # Example
+///
+/// ```
+/// # use neli_wifi::{ApSettings, BeaconData, ChannelDef};
+/// # let (head, tail) = (vec![], vec![]);
+/// let settings = ApSettings::new(b"portal", BeaconData::new(&head, &tail))
+///     .channel(ChannelDef::new(2437))
+///     .inactivity_timeout(300);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApSettings {
+    ssid: Vec<u8>,
+    beacon: BeaconData,
+    beacon_interval: u32,
+    dtim_period: u32,
+    hidden_ssid: Option<Nl80211HiddenSsid>,
+    channel: Option<ChannelDef>,
+    auth_type: Option<Nl80211AuthType>,
+    privacy: bool,
+    wpa_versions: Option<u32>,
+    pairwise_ciphers: Option<Vec<u32>>,
+    group_cipher: Option<u32>,
+    akm_suites: Option<Vec<u32>>,
+    inactivity_timeout: Option<u16>,
+    smps_mode: Option<Nl80211SmpsMode>,
+    acl: Option<MacAcl>,
+}
+
+impl ApSettings {
+    /// AP of the network `ssid` sending `beacon` every 100 TU, with a DTIM period of 2
+    pub fn new(ssid: &[u8], beacon: BeaconData) -> Self {
+        Self {
+            ssid: ssid.to_vec(),
+            beacon,
+            beacon_interval: 100,
+            dtim_period: 2,
+            hidden_ssid: None,
+            channel: None,
+            auth_type: None,
+            privacy: false,
+            wpa_versions: None,
+            pairwise_ciphers: None,
+            group_cipher: None,
+            akm_suites: None,
+            inactivity_timeout: None,
+            smps_mode: None,
+            acl: None,
+        }
+    }
+
+    /// Beacon interval in TU
+    pub fn beacon_interval(mut self, interval: u32) -> Self {
+        self.beacon_interval = interval;
+        self
+    }
+
+    /// DTIM period in beacon intervals
+    pub fn dtim_period(mut self, period: u32) -> Self {
+        self.dtim_period = period;
+        self
+    }
+
+    /// Hide the SSID in beacons
+    pub fn hidden_ssid(mut self, hidden_ssid: Nl80211HiddenSsid) -> Self {
+        self.hidden_ssid = Some(hidden_ssid);
+        self
+    }
+
+    /// Operating channel, the current one is used if not set
+    pub fn channel(mut self, channel: ChannelDef) -> Self {
+        self.channel = Some(channel);
+        self
+    }
+
+    /// Authentication type
+    pub fn auth_type(mut self, auth_type: Nl80211AuthType) -> Self {
+        self.auth_type = Some(auth_type);
+        self
+    }
+
+    /// Protect the data frames
+    pub fn privacy(mut self, privacy: bool) -> Self {
+        self.privacy = privacy;
+        self
+    }
+
+    /// Allowed WPA versions
+    pub fn wpa_versions(mut self, versions: &[Nl80211WpaVersions]) -> Self {
+        self.wpa_versions = Some(versions.iter().map(|v| u32::from(u16::from(v))).sum());
+        self
+    }
+
+    /// Pairwise cipher suites
+    pub fn pairwise_ciphers(mut self, ciphers: &[u32]) -> Self {
+        self.pairwise_ciphers = Some(ciphers.to_vec());
+        self
+    }
+
+    /// Group cipher suite
+    pub fn group_cipher(mut self, cipher: u32) -> Self {
+        self.group_cipher = Some(cipher);
+        self
+    }
+
+    /// AKM suites
+    pub fn akm_suites(mut self, suites: &[u32]) -> Self {
+        self.akm_suites = Some(suites.to_vec());
+        self
+    }
+
+    /// Time in seconds after which inactive stations are disconnected
+    pub fn inactivity_timeout(mut self, timeout: u16) -> Self {
+        self.inactivity_timeout = Some(timeout);
+        self
+    }
+
+    /// Spatial multiplexing power save mode
+    pub fn smps_mode(mut self, mode: Nl80211SmpsMode) -> Self {
+        self.smps_mode = Some(mode);
+        self
+    }
+
+    /// MAC address based access control
+    pub fn acl(mut self, acl: MacAcl) -> Self {
+        self.acl = Some(acl);
+        self
+    }
+
+    pub(crate) fn append_attrs(
+        &self,
+        attrs: &mut GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), SerError> {
+        self.beacon.append_attrs(attrs)?;
+        attrs.push(new_attr(Nl80211Attr::AttrSsid, self.ssid.as_slice())?);
+        attrs.push(new_attr(
+            Nl80211Attr::AttrBeaconInterval,
+            self.beacon_interval,
+        )?);
+        attrs.push(new_attr(Nl80211Attr::AttrDtimPeriod, self.dtim_period)?);
+        if let Some(hidden_ssid) = self.hidden_ssid {
+            attrs.push(new_attr(
+                Nl80211Attr::AttrHiddenSsid,
+                u32::from(u16::from(hidden_ssid)),
+            )?);
+        }
+        if let Some(channel) = &self.channel {
+            channel.append_attrs(attrs)?;
+        }
+        if let Some(auth_type) = self.auth_type {
+            attrs.push(new_attr(
+                Nl80211Attr::AttrAuthType,
+                u32::from(u16::from(auth_type)),
+            )?);
+        }
+        if self.privacy {
+            attrs.push(new_attr(Nl80211Attr::AttrPrivacy, ())?);
+        }
+        if let Some(versions) = self.wpa_versions {
+            attrs.push(new_attr(Nl80211Attr::AttrWpaVersions, versions)?);
+        }
+        if let Some(ciphers) = &self.pairwise_ciphers {
+            attrs.push(new_attr(
+                Nl80211Attr::AttrCipherSuitesPairwise,
+                ciphers.clone(),
+            )?);
+        }
+        if let Some(cipher) = self.group_cipher {
+            attrs.push(new_attr(Nl80211Attr::AttrCipherSuiteGroup, cipher)?);
+        }
+        if let Some(suites) = &self.akm_suites {
+            attrs.push(new_attr(Nl80211Attr::AttrAkmSuites, suites.clone())?);
+        }
+        if let Some(timeout) = self.inactivity_timeout {
+            attrs.push(new_attr(Nl80211Attr::AttrInactivityTimeout, timeout)?);
+        }
+        if let Some(mode) = self.smps_mode {
+            attrs.push(new_attr(Nl80211Attr::AttrSmpsMode, u16::from(mode) as u8)?);
+        }
+        if let Some(acl) = &self.acl {
+            acl.append_attrs(attrs)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parameters of a channel switch announced by an AP, mesh or IBSS interface
+///
+/// AP and P2P GO interfaces require both the beacon to send after the switch and the beacon sent
+/// during the countdown, even when `count` is 0. The latter comes with the offsets of the counters
+/// of the channel switch announcement elements in its tail, at least one, and in its probe
+/// response template.
+///
+/// # Example
+///
+/// ```
+/// # use neli_wifi::{BeaconBuilder, ChannelDef, ChannelSwitchParams};
+/// let bssid = [0x02, 0, 0, 0, 1, 0];
+/// let after = BeaconBuilder::new(&bssid, b"portal").build();
+/// // Channel switch announcement element: mode, new channel and counter
+/// let during = BeaconBuilder::new(&bssid, b"portal").tail_ies(&[0x25, 3, 1, 36, 5]).build();
+/// let params = ChannelSwitchParams::new(ChannelDef::new(5180), 5)
+///     .block_tx(true)
+///     .beacon_after(after)
+///     .csa_beacon(during, &[4], &[]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelSwitchParams {
+    channel: ChannelDef,
+    count: u32,
+    block_tx: bool,
+    beacon_after: Option<BeaconData>,
+    csa_beacon: Option<(BeaconData, Vec<u16>, Vec<u16>)>,
+}
+
+impl ChannelSwitchParams {
+    /// Switch to `channel` after `count` beacon intervals, 0 switching immediately
+    pub fn new(channel: ChannelDef, count: u32) -> Self {
+        Self {
+            channel,
+            count,
+            block_tx: false,
+            beacon_after: None,
+            csa_beacon: None,
+        }
+    }
+
+    /// Ask the associated stations to stop transmitting until the switch
+    pub fn block_tx(mut self, block_tx: bool) -> Self {
+        self.block_tx = block_tx;
+        self
+    }
+
+    /// Beacon sent on the new channel
+    pub fn beacon_after(mut self, beacon: BeaconData) -> Self {
+        self.beacon_after = Some(beacon);
+        self
+    }
+
+    /// Beacon sent during the countdown, with the offsets of the counters in its tail and in its
+    /// probe response template, required for AP and P2P GO interfaces
+    pub fn csa_beacon(
+        mut self,
+        beacon: BeaconData,
+        beacon_offsets: &[u16],
+        probe_resp_offsets: &[u16],
+    ) -> Self {
+        self.csa_beacon = Some((beacon, beacon_offsets.to_vec(), probe_resp_offsets.to_vec()));
+        self
+    }
+
+    pub(crate) fn append_attrs(
+        &self,
+        attrs: &mut GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), SerError> {
+        attrs.push(new_attr(Nl80211Attr::AttrChSwitchCount, self.count)?);
+        if self.block_tx {
+            attrs.push(new_attr(Nl80211Attr::AttrChSwitchBlockTx, ())?);
+        }
+        self.channel.append_attrs(attrs)?;
+        if let Some(beacon) = &self.beacon_after {
+            beacon.append_attrs(attrs)?;
+        }
+        if let Some((beacon, beacon_offsets, probe_resp_offsets)) = &self.csa_beacon {
+            if beacon_offsets.is_empty() {
+                return Err(SerError::new("Missing counter offset in the CSA beacon"));
+            }
+            let mut csa_attrs = beacon.attrs()?;
+            csa_attrs.push(new_attr(
+                Nl80211Attr::AttrCsaCOffBeacon,
+                beacon_offsets.clone(),
+            )?);
+            if !probe_resp_offsets.is_empty() {
+                csa_attrs.push(new_attr(
+                    Nl80211Attr::AttrCsaCOffPresp,
+                    probe_resp_offsets.clone(),
+                )?);
+            }
+            attrs.push(new_nested_attr(Nl80211Attr::AttrCsaIes, &csa_attrs)?);
+        }
+        Ok(())
+    }
+}
+
+/// TX status of the frame sent by [`Socket::probe_client`](crate::Socket::probe_client)
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ProbeStatus {
+    /// MAC address of the probed station
+    pub mac: Option<Vec<u8>>,
+    /// Cookie identifying the probe
+    pub cookie: Option<u64>,
+    /// The station acknowledged the frame
+    pub acked: bool,
+    /// Signal strength of the acknowledgement (dBm)
+    pub ack_signal: Option<i32>,
+}
+
+impl TryFrom<Attrs<'_, Nl80211Attr>> for ProbeStatus {
+    type Error = DeError;
+
+    fn try_from(attrs: Attrs<'_, Nl80211Attr>) -> Result<Self, Self::Error> {
+        let mut res = Self::default();
+        for attr in attrs.iter() {
+            match attr.nla_type.nla_type {
+                Nl80211Attr::AttrMac => res.mac = Some(attr.get_payload_as_with_len()?),
+                Nl80211Attr::AttrCookie => res.cookie = Some(attr.get_payload_as()?),
+                Nl80211Attr::AttrAck => res.acked = true,
+                Nl80211Attr::AttrAckSignal => res.ack_signal = Some(attr.get_payload_as()?),
+                _ => (),
+            }
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod test_probe_status {
+    use super::*;
+    use crate::attr::Nl80211Attr::*;
+    use neli::attr::AttrHandle;
+    use neli::genl::{AttrType, Nlattr};
+    use neli::types::Buffer;
+
+    fn new_attr(t: Nl80211Attr, d: Vec<u8>) -> Nlattr<Nl80211Attr, Buffer> {
+        Nlattr {
+            nla_len: (4 + d.len()) as _,
+            nla_type: AttrType {
+                nla_nested: false,
+                nla_network_order: true,
+                nla_type: t,
+            },
+            nla_payload: d.into(),
+        }
+    }
+
+    #[test]
+    fn test_parser() {
+        let handler = vec![
+            new_attr(AttrWiphy, vec![0, 0, 0, 0]),
+            new_attr(AttrIfindex, vec![3, 0, 0, 0]),
+            new_attr(AttrMac, vec![2, 0, 0, 0, 1, 0]),
+            new_attr(AttrCookie, vec![42, 0, 0, 0, 0, 0, 0, 0]),
+            new_attr(AttrAckSignal, vec![198, 255, 255, 255]),
+            new_attr(AttrAck, vec![]),
+        ];
+
+        let status: ProbeStatus = AttrHandle::new(handler.into_iter().collect())
+            .try_into()
+            .unwrap();
+        let expected_status = ProbeStatus {
+            mac: Some(vec![2, 0, 0, 0, 1, 0]),
+            cookie: Some(42),
+            acked: true,
+            ack_signal: Some(-58),
+        };
+
+        assert_eq!(status, expected_status)
+    }
+}
+
+#[cfg(test)]
+mod test_mac_acl {
+    use super::*;
+    use neli::ToBytes;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_attrs() {
+        let acl = MacAcl::new(Nl80211AclPolicy::AclPolicyDenyUnlessListed)
+            .mac(&[0x02, 0, 0, 0, 1, 0])
+            .mac(&[0x02, 0, 0, 0, 2, 0]);
+        let mut attrs = GenlBuffer::new();
+        acl.append_attrs(&mut attrs).unwrap();
+        let mut bytes = Cursor::new(Vec::new());
+        attrs.to_bytes(&mut bytes).unwrap();
+
+        assert_eq!(
+            bytes.into_inner(),
+            vec![
+                8, 0, 165, 0, 1, 0, 0, 0, // policy
+                28, 0, 166, 128, // MAC addresses
+                10, 0, 1, 0, 2, 0, 0, 0, 1, 0, 0, 0, // first one
+                10, 0, 2, 0, 2, 0, 0, 0, 2, 0, 0, 0, // second one
+            ]
+        );
+    }
+}
@@ -1,20 +1,56 @@
+use crate::attr::{new_attr, new_nested_attr};
+use crate::interface::index_from_name;
+use crate::socket::{convert_nlmsgerr, is_reply_to, nl80211_request, EVENT_TIMEOUT};
+use crate::ApSettings;
+use crate::AssociateParams;
 use crate::Attrs;
+use crate::AuthenticateParams;
+use crate::BeaconData;
+use crate::BitrateMask;
 use crate::Bss;
+use crate::BssParams;
+use crate::ChannelDef;
+use crate::ChannelSwitchParams;
+use crate::ConnectParams;
+use crate::Event;
 use crate::Interface;
+use crate::InterfaceId;
+use crate::InterfaceOptions;
+use crate::JoinMeshParams;
+use crate::Key;
+use crate::KeyParams;
+use crate::MacAcl;
+use crate::MeshConfig;
+use crate::MeshPath;
+use crate::MlmeResult;
 use crate::Nl80211Attr;
 use crate::Nl80211Cmd;
+use crate::Nl80211Iftype;
+use crate::Nl80211KeyAttributes;
+use crate::Nl80211KeyDefaultTypes;
+use crate::Nl80211MntrFlags;
+use crate::Nl80211PsState;
+use crate::Nl80211TxPowerSetting;
+use crate::PmksaParams;
+use crate::ProbeStatus;
+use crate::RegDomain;
+use crate::RekeyParams;
+use crate::SetInterfaceError;
 use crate::Socket;
 use crate::Station;
-use crate::NL_80211_GENL_VERSION;
+use crate::WiphyId;
+use crate::WiphyParams;
 use neli::err::DeError;
 
+use neli::attr::Attribute;
 use neli::consts::genl::{CtrlAttr, CtrlCmd};
-use neli::consts::{nl::GenlId, nl::NlmF, nl::NlmFFlags, nl::Nlmsg};
+use neli::consts::{nl::GenlId, nl::Nlmsg};
 use neli::err::NlError;
-use neli::genl::{Genlmsghdr, Nlattr};
-use neli::nl::{NlPayload, Nlmsghdr};
+use neli::genl::Genlmsghdr;
+use neli::nl::NlPayload;
 use neli::socket::tokio::NlSocket;
-use neli::types::GenlBuffer;
+use neli::types::{Buffer, GenlBuffer};
+use tokio::time::{timeout_at, Instant};
 
 /// A generic netlink socket to send commands and receive messages
 #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
@@ -40,33 +76,60 @@ impl AsyncSocket {
         Ok(Socket::connect()?.try_into()?)
     }
 
-    async fn get_info_vec<T>(
+    /// Create a new nl80211 socket receiving the notifications of the "mlme" multicast group
+    ///
+    /// The notifications are read with [`AsyncSocket::next_event`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{AsyncSocket, Event};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let mut events = AsyncSocket::connect_mlme_events()?;
+    /// loop {
+    ///     if let Event::Disconnect { interface_index, .. } = events.next_event().await? {
+    ///         println!("interface {} disconnected", interface_index);
+    ///     }
+    /// }
+    /// # }
+    ///```
+    pub fn connect_mlme_events() -> Result<Self, NlError> {
+        Ok(Socket::subscribe("mlme")?.try_into()?)
+    }
+
+    /// Wait for the next notification
+    ///
+    /// Notifications that cannot be decoded or represented as an [`Event`] are skipped.
+    pub async fn next_event(&mut self) -> Result<Event, NlError> {
+        let mut buf = Vec::new();
+
+        loop {
+            let res = self
+                .sock
+                .recv::<u16, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>(&mut buf)
+                .await?;
+            for response in res {
+                if let NlPayload::Payload(msg) = response.nl_payload {
+                    if let Some(event) = Event::from_msg(&msg) {
+                        return Ok(event);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Send a command and collect the messages sent back by the kernel
+    ///
+    /// Non-dump requests ask for an acknowledgement, so errors reported by the kernel are
+    /// returned as [`NlError::Nlmsgerr`].
+    async fn request(
         &mut self,
-        interface_index: Option<i32>,
         cmd: Nl80211Cmd,
-    ) -> Result<Vec<T>, NlError>
-    where
-        T: for<'a> TryFrom<Attrs<'a, Nl80211Attr>, Error = DeError>,
-    {
-        let msghdr = Genlmsghdr::<Nl80211Cmd, Nl80211Attr>::new(cmd, NL_80211_GENL_VERSION, {
-            let mut attrs = GenlBuffer::new();
-            if let Some(interface_index) = interface_index {
-                attrs.push(
-                    Nlattr::new(false, false, Nl80211Attr::AttrIfindex, interface_index).unwrap(),
-                );
-            }
-            attrs
-        });
-
-        let nlhdr = {
-            let len = None;
-            let nl_type = self.family_id;
-            let flags = NlmFFlags::new(&[NlmF::Request, NlmF::Dump]);
-            let seq = None;
-            let pid = None;
-            let payload = NlPayload::Payload(msghdr);
-            Nlmsghdr::new(len, nl_type, flags, seq, pid, payload)
-        };
+        attrs: GenlBuffer<Nl80211Attr, Buffer>,
+        dump: bool,
+    ) -> Result<Vec<Genlmsghdr<Nl80211Cmd, Nl80211Attr>>, NlError> {
+        let nlhdr = nl80211_request(self.family_id, cmd, attrs, dump);
 
         self.sock.send(&nlhdr).await?;
 
@@ -79,25 +142,89 @@ impl AsyncSocket {
                 .recv::<Nlmsg, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>(&mut buf)
                 .await?;
             for response in res {
-                match response.nl_type {
-                    Nlmsg::Noop => (),
-                    Nlmsg::Error => panic!("Error"),
-                    Nlmsg::Done => return Ok(retval),
-                    _ => {
-                        retval.push(
-                            response
-                                .nl_payload
-                                .get_payload()
-                                .unwrap()
-                                .get_attr_handle()
-                                .try_into()?,
-                        );
-                    }
+                match response.nl_payload {
+                    NlPayload::Err(err) => return Err(convert_nlmsgerr(err)),
+                    NlPayload::Ack(_) => return Ok(retval),
+                    _ if response.nl_type == Nlmsg::Done => return Ok(retval),
+                    NlPayload::Payload(payload) => retval.push(payload),
+                    NlPayload::Empty => (),
                 };
             }
         }
     }
 
+    async fn get_info_vec<T>(
+        &mut self,
+        cmd: Nl80211Cmd,
+        attrs: GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<Vec<T>, NlError>
+    where
+        T: for<'a> TryFrom<Attrs<'a, Nl80211Attr>, Error = DeError>,
+    {
+        let mut retval = Vec::new();
+        for msg in self.request(cmd, attrs, true).await? {
+            retval.push(msg.get_attr_handle().try_into()?);
+        }
+        Ok(retval)
+    }
+
+    async fn get_info<T>(
+        &mut self,
+        cmd: Nl80211Cmd,
+        attrs: GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<T, NlError>
+    where
+        T: for<'a> TryFrom<Attrs<'a, Nl80211Attr>, Error = DeError>,
+    {
+        match self.request(cmd, attrs, false).await?.first() {
+            Some(msg) => Ok(msg.get_attr_handle().try_into()?),
+            None => Err(NlError::msg("No response received")),
+        }
+    }
+
+    async fn send_cmd(
+        &mut self,
+        cmd: Nl80211Cmd,
+        attrs: GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), NlError> {
+        self.request(cmd, attrs, false).await?;
+        Ok(())
+    }
+
+    /// Send an MLME command and wait for the notification carrying its result
+    ///
+    /// Gives up with [`MlmeResult::TimedOut`] if it doesn't arrive within [`EVENT_TIMEOUT`].
+    async fn mlme_request(
+        &mut self,
+        cmd: Nl80211Cmd,
+        interface_index: i32,
+        attrs: GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<MlmeResult, NlError> {
+        // Subscribe before sending the command so that the result cannot be missed
+        let mut events = Self::try_from(Socket::subscribe("mlme")?)?;
+        self.send_cmd(cmd, attrs).await?;
+
+        let deadline = Instant::now() + EVENT_TIMEOUT;
+        let mut buf = Vec::new();
+
+        loop {
+            let recv = events
+                .sock
+                .recv::<u16, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>(&mut buf);
+            let res = match timeout_at(deadline, recv).await {
+                Ok(res) => res?,
+                Err(_) => return Ok(MlmeResult::TimedOut),
+            };
+            for response in res {
+                if let NlPayload::Payload(msg) = response.nl_payload {
+                    if is_reply_to(&msg, cmd, interface_index) {
+                        return Ok(msg.get_attr_handle().try_into()?);
+                    }
+                }
+            }
+        }
+    }
+
     /// Get information for all your wifi interfaces
     ///
     /// # Example
@@ -114,7 +241,39 @@ impl AsyncSocket {
     /// # };
     ///```
     pub async fn get_interfaces_info(&mut self) -> Result<Vec<Interface>, NlError> {
-        self.get_info_vec(None, Nl80211Cmd::CmdGetInterface).await
+        self.get_info_vec(Nl80211Cmd::CmdGetInterface, GenlBuffer::new())
+            .await
+    }
+
+    /// Get information for a single wifi interface
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::AsyncSocket;
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let wifi_interface = AsyncSocket::connect()?.get_interface_by_name("wlan0").await?;
+    /// println!("{:#?}", wifi_interface);
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn get_interface(&mut self, interface_index: i32) -> Result<Interface, NlError> {
+        let mut attrs = GenlBuffer::new();
+        InterfaceId::Index(interface_index).append_attrs(&mut attrs)?;
+        self.get_info(Nl80211Cmd::CmdGetInterface, attrs).await
+    }
+
+    /// Get information for the wifi interface `name`
+    pub async fn get_interface_by_name(&mut self, name: &str) -> Result<Interface, NlError> {
+        self.get_interface(index_from_name(name)?).await
+    }
+
+    /// Get information for the wireless device `wdev`, which may not have a netdev
+    pub async fn get_interface_by_wdev(&mut self, wdev: u64) -> Result<Interface, NlError> {
+        let mut attrs = GenlBuffer::new();
+        InterfaceId::Wdev(wdev).append_attrs(&mut attrs)?;
+        self.get_info(Nl80211Cmd::CmdGetInterface, attrs).await
     }
 
     /// Get access point information for a specific interface
@@ -143,12 +302,969 @@ impl AsyncSocket {
         &mut self,
         interface_index: i32,
     ) -> Result<Vec<Station>, NlError> {
-        self.get_info_vec(Some(interface_index), Nl80211Cmd::CmdGetStation)
-            .await
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        self.get_info_vec(Nl80211Cmd::CmdGetStation, attrs).await
     }
 
     pub async fn get_bss_info(&mut self, interface_index: i32) -> Result<Vec<Bss>, NlError> {
-        self.get_info_vec(Some(interface_index), Nl80211Cmd::CmdGetScan)
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        self.get_info_vec(Nl80211Cmd::CmdGetScan, attrs).await
+    }
+
+    /// Get the mesh paths known to a mesh point interface
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::AsyncSocket;
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let mut socket = AsyncSocket::connect()?;
+    /// for mesh_path in socket.get_mesh_paths(3).await? {
+    ///     println!("{:?} via {:?}", mesh_path.destination, mesh_path.next_hop);
+    /// }
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn get_mesh_paths(&mut self, interface_index: i32) -> Result<Vec<MeshPath>, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        self.get_info_vec(Nl80211Cmd::CmdGetMpath, attrs).await
+    }
+
+    /// Get the mesh proxy paths of a mesh point interface
+    ///
+    /// For proxy paths, [`MeshPath::next_hop`] is the mesh proxy (gate) the destination is
+    /// reachable through.
+    pub async fn get_mesh_proxy_paths(
+        &mut self,
+        interface_index: i32,
+    ) -> Result<Vec<MeshPath>, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        self.get_info_vec(Nl80211Cmd::CmdGetMpp, attrs).await
+    }
+
+    /// Add a static mesh path to `destination` through `next_hop`
+    pub async fn new_mesh_path(
+        &mut self,
+        interface_index: i32,
+        destination: &[u8],
+        next_hop: &[u8],
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrMac, destination)?);
+        attrs.push(new_attr(Nl80211Attr::AttrMpathNextHop, next_hop)?);
+        self.send_cmd(Nl80211Cmd::CmdNewMpath, attrs).await
+    }
+
+    /// Change the next hop of an existing mesh path
+    pub async fn set_mesh_path(
+        &mut self,
+        interface_index: i32,
+        destination: &[u8],
+        next_hop: &[u8],
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrMac, destination)?);
+        attrs.push(new_attr(Nl80211Attr::AttrMpathNextHop, next_hop)?);
+        self.send_cmd(Nl80211Cmd::CmdSetMpath, attrs).await
+    }
+
+    /// Delete the mesh path to `destination`
+    pub async fn del_mesh_path(
+        &mut self,
+        interface_index: i32,
+        destination: &[u8],
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrMac, destination)?);
+        self.send_cmd(Nl80211Cmd::CmdDelMpath, attrs).await
+    }
+
+    /// Join an 802.11s mesh
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{AsyncSocket, ChannelDef, JoinMeshParams};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let params = JoinMeshParams::new(b"backhaul")
+    ///     .channel(ChannelDef::new(2412))
+    ///     .forwarding(true);
+    /// AsyncSocket::connect()?.join_mesh(3, &params).await?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn join_mesh(
+        &mut self,
+        interface_index: i32,
+        params: &JoinMeshParams,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        params.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdJoinMesh, attrs).await
+    }
+
+    /// Leave the mesh the interface is part of
+    pub async fn leave_mesh(&mut self, interface_index: i32) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        self.send_cmd(Nl80211Cmd::CmdLeaveMesh, attrs).await
+    }
+
+    /// Get the configuration of a mesh point interface
+    pub async fn get_mesh_config(&mut self, interface_index: i32) -> Result<MeshConfig, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        self.get_info(Nl80211Cmd::CmdGetMeshConfig, attrs).await
+    }
+
+    /// Change the configuration of a mesh point interface
+    ///
+    /// Only the fields of `config` that are set are changed.
+    pub async fn set_mesh_config(
+        &mut self,
+        interface_index: i32,
+        config: &MeshConfig,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_nested_attr(
+            Nl80211Attr::AttrMeshConfig,
+            &config.attrs()?,
+        )?);
+        self.send_cmd(Nl80211Cmd::CmdSetMeshConfig, attrs).await
+    }
+
+    /// Connect to a network using the SME implemented by the driver
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{ConnectParams, AsyncSocket};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// AsyncSocket::connect()?.connect_network(3, &ConnectParams::new(b"guest")).await?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn connect_network(
+        &mut self,
+        interface_index: i32,
+        params: &ConnectParams,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        params.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdConnect, attrs).await
+    }
+
+    /// Disconnect from the current network with the given reason code
+    pub async fn disconnect(&mut self, interface_index: i32, reason: u16) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrReasonCode, reason)?);
+        self.send_cmd(Nl80211Cmd::CmdDisconnect, attrs).await
+    }
+
+    /// Authenticate with a BSS and wait for its answer
+    ///
+    /// Waits for up to 5 seconds, [`MlmeResult::TimedOut`] being returned if no answer is
+    /// reported in time.
+    ///
+    /// This is used by userspace SMEs, for drivers without one.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{AuthenticateParams, MlmeResult, Nl80211AuthType, AsyncSocket};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let bssid = [0x02, 0, 0, 0, 1, 0];
+    /// let params =
+    ///     AuthenticateParams::new(&bssid, 2412, b"home", Nl80211AuthType::AuthtypeOpenSystem);
+    /// match AsyncSocket::connect()?.authenticate(3, &params).await? {
+    ///     MlmeResult::Response(frame) => println!("authenticated: {:?}", frame),
+    ///     _ => println!("no answer"),
+    /// }
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn authenticate(
+        &mut self,
+        interface_index: i32,
+        params: &AuthenticateParams,
+    ) -> Result<MlmeResult, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        params.append_attrs(&mut attrs)?;
+        self.mlme_request(Nl80211Cmd::CmdAuthenticate, interface_index, attrs)
+            .await
+    }
+
+    /// Associate with a BSS we are authenticated with and wait for its answer
+    pub async fn associate(
+        &mut self,
+        interface_index: i32,
+        params: &AssociateParams,
+    ) -> Result<MlmeResult, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        params.append_attrs(&mut attrs)?;
+        self.mlme_request(Nl80211Cmd::CmdAssociate, interface_index, attrs)
+            .await
+    }
+
+    /// Deauthenticate from a BSS with the given reason code
+    pub async fn deauthenticate(
+        &mut self,
+        interface_index: i32,
+        bssid: &[u8],
+        reason: u16,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrMac, bssid)?);
+        attrs.push(new_attr(Nl80211Attr::AttrReasonCode, reason)?);
+        self.send_cmd(Nl80211Cmd::CmdDeauthenticate, attrs).await
+    }
+
+    /// Disassociate from a BSS with the given reason code
+    pub async fn disassociate(
+        &mut self,
+        interface_index: i32,
+        bssid: &[u8],
+        reason: u16,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrMac, bssid)?);
+        attrs.push(new_attr(Nl80211Attr::AttrReasonCode, reason)?);
+        self.send_cmd(Nl80211Cmd::CmdDisassociate, attrs).await
+    }
+
+    /// Install a key
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{KeyParams, Nl80211KeyDefaultTypes, AsyncSocket};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let mut socket = AsyncSocket::connect()?;
+    /// socket.new_key(3, &KeyParams::new(1, 0x000fac04, &[0; 16])).await?;
+    /// socket.set_default_key(3, 1, &[Nl80211KeyDefaultTypes::KeyDefaultTypeMulticast]).await?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn new_key(&mut self, interface_index: i32, key: &KeyParams) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        key.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdNewKey, attrs).await
+    }
+
+    /// Get the key stored at `key_index`, the pairwise key of the peer `mac` if given
+    ///
+    /// This is mostly useful to read the current TX sequence counter of a key.
+    pub async fn get_key(
+        &mut self,
+        interface_index: i32,
+        key_index: u8,
+        mac: Option<&[u8]>,
+    ) -> Result<Key, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrKeyIdx, key_index)?);
+        if let Some(mac) = mac {
+            attrs.push(new_attr(Nl80211Attr::AttrMac, mac)?);
+        }
+        self.get_info(Nl80211Cmd::CmdGetKey, attrs).await
+    }
+
+    /// Delete the key stored at `key_index`, the pairwise key of the peer `mac` if given
+    pub async fn del_key(
+        &mut self,
+        interface_index: i32,
+        key_index: u8,
+        mac: Option<&[u8]>,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrKeyIdx, key_index)?);
+        if let Some(mac) = mac {
+            attrs.push(new_attr(Nl80211Attr::AttrMac, mac)?);
+        }
+        self.send_cmd(Nl80211Cmd::CmdDelKey, attrs).await
+    }
+
+    /// Use the key stored at `key_index` as default key to transmit data frames
+    ///
+    /// The key is used for both unicast and multicast frames if `types` is empty.
+    pub async fn set_default_key(
+        &mut self,
+        interface_index: i32,
+        key_index: u8,
+        types: &[Nl80211KeyDefaultTypes],
+    ) -> Result<(), NlError> {
+        let mut key = vec![
+            new_attr(Nl80211KeyAttributes::KeyIdx, key_index)?,
+            new_attr(Nl80211KeyAttributes::KeyDefault, ())?,
+        ];
+        if !types.is_empty() {
+            let types = types
+                .iter()
+                .map(|t| new_attr(*t, ()))
+                .collect::<Result<Vec<_>, _>>()?;
+            key.push(new_nested_attr(
+                Nl80211KeyAttributes::KeyDefaultTypes,
+                &types,
+            )?);
+        }
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_nested_attr(Nl80211Attr::AttrKey, &key)?);
+        self.send_cmd(Nl80211Cmd::CmdSetKey, attrs).await
+    }
+
+    /// Use the IGTK stored at `key_index` to protect transmitted management frames
+    pub async fn set_default_mgmt_key(
+        &mut self,
+        interface_index: i32,
+        key_index: u8,
+    ) -> Result<(), NlError> {
+        let key = [
+            new_attr(Nl80211KeyAttributes::KeyIdx, key_index)?,
+            new_attr(Nl80211KeyAttributes::KeyDefaultMgmt, ())?,
+        ];
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_nested_attr(Nl80211Attr::AttrKey, &key)?);
+        self.send_cmd(Nl80211Cmd::CmdSetKey, attrs).await
+    }
+
+    /// Use the BIGTK stored at `key_index` to protect transmitted beacons
+    pub async fn set_default_beacon_key(
+        &mut self,
+        interface_index: i32,
+        key_index: u8,
+    ) -> Result<(), NlError> {
+        let key = [
+            new_attr(Nl80211KeyAttributes::KeyIdx, key_index)?,
+            new_attr(Nl80211KeyAttributes::KeyDefaultBeacon, ())?,
+        ];
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_nested_attr(Nl80211Attr::AttrKey, &key)?);
+        self.send_cmd(Nl80211Cmd::CmdSetKey, attrs).await
+    }
+
+    /// Add a PMKSA cache entry to the driver
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{PmksaParams, AsyncSocket};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let entry = PmksaParams::new(&[0; 16]).bssid(&[0x02, 0, 0, 0, 1, 0]);
+    /// AsyncSocket::connect()?.set_pmksa(3, &entry).await?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn set_pmksa(
+        &mut self,
+        interface_index: i32,
+        entry: &PmksaParams,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        entry.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdSetPmksa, attrs).await
+    }
+
+    /// Delete a PMKSA cache entry from the driver
+    pub async fn del_pmksa(
+        &mut self,
+        interface_index: i32,
+        entry: &PmksaParams,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        entry.append_id_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdDelPmksa, attrs).await
+    }
+
+    /// Delete all the PMKSA cache entries of the driver
+    pub async fn flush_pmksa(&mut self, interface_index: i32) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        self.send_cmd(Nl80211Cmd::CmdFlushPmksa, attrs).await
+    }
+
+    /// Give the device the keys it needs to handle group rekeys while the host is asleep
+    ///
+    /// The device reports completed rekeys with [`Event::GtkRekey`](crate::Event::GtkRekey).
+    pub async fn set_rekey_offload(
+        &mut self,
+        interface_index: i32,
+        rekey: &RekeyParams,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        rekey.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdSetRekeyOffload, attrs).await
+    }
+
+    /// Create a new interface on the wiphy `phy` and return it
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{InterfaceOptions, Nl80211Iftype, AsyncSocket};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let options = InterfaceOptions::new();
+    /// let interface = AsyncSocket::connect()?
+    ///     .create_interface(0, "mon0", Nl80211Iftype::IftypeMonitor, &options).await?;
+    /// println!("{:?}", interface.index);
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn create_interface(
+        &mut self,
+        phy: u32,
+        name: &str,
+        iftype: Nl80211Iftype,
+        options: &InterfaceOptions,
+    ) -> Result<Interface, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrWiphy, phy)?);
+        attrs.push(new_attr(Nl80211Attr::AttrIfname, name)?);
+        attrs.push(new_attr(
+            Nl80211Attr::AttrIftype,
+            u32::from(u16::from(iftype)),
+        )?);
+        options.append_attrs(&mut attrs)?;
+        self.get_info(Nl80211Cmd::CmdNewInterface, attrs).await
+    }
+
+    /// Delete an interface
+    pub async fn delete_interface(&mut self, id: InterfaceId) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        id.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdDelInterface, attrs).await
+    }
+
+    /// Change the type of an interface
+    ///
+    /// Most drivers require the interface to be down, [`SetInterfaceError::Busy`] is returned
+    /// otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{Nl80211Iftype, SetInterfaceError, AsyncSocket};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let mut socket = AsyncSocket::connect()?;
+    /// match socket.set_interface_type(3, Nl80211Iftype::IftypeAp).await {
+    ///     Err(SetInterfaceError::Busy) => println!("bring the interface down first"),
+    ///     res => res?,
+    /// }
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn set_interface_type(
+        &mut self,
+        interface_index: i32,
+        iftype: Nl80211Iftype,
+    ) -> Result<(), SetInterfaceError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(
+            Nl80211Attr::AttrIftype,
+            u32::from(u16::from(iftype)),
+        )?);
+        Ok(self.send_cmd(Nl80211Cmd::CmdSetInterface, attrs).await?)
+    }
+
+    /// Enable or disable 4-address frames on an interface
+    pub async fn set_four_addr(
+        &mut self,
+        interface_index: i32,
+        four_addr: bool,
+    ) -> Result<(), SetInterfaceError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        InterfaceOptions::new()
+            .four_addr(four_addr)
+            .append_attrs(&mut attrs)?;
+        Ok(self.send_cmd(Nl80211Cmd::CmdSetInterface, attrs).await?)
+    }
+
+    /// Set the flags of a monitor interface
+    pub async fn set_monitor_flags(
+        &mut self,
+        interface_index: i32,
+        flags: &[Nl80211MntrFlags],
+    ) -> Result<(), SetInterfaceError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        InterfaceOptions::new()
+            .monitor_flags(flags)
+            .append_attrs(&mut attrs)?;
+        Ok(self.send_cmd(Nl80211Cmd::CmdSetInterface, attrs).await?)
+    }
+
+    /// Set the mesh ID of a mesh point interface
+    pub async fn set_interface_mesh_id(
+        &mut self,
+        interface_index: i32,
+        mesh_id: &[u8],
+    ) -> Result<(), SetInterfaceError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrMeshId, mesh_id)?);
+        Ok(self.send_cmd(Nl80211Cmd::CmdSetInterface, attrs).await?)
+    }
+
+    /// Set the operating channel of a monitor, AP or mesh interface
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{ChannelDef, AsyncSocket};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let mut socket = AsyncSocket::connect()?;
+    /// for frequency in [2412, 2437, 2462] {
+    ///     socket.set_channel(3, &ChannelDef::new(frequency)).await?;
+    /// }
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn set_channel(
+        &mut self,
+        interface_index: i32,
+        channel: &ChannelDef,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        channel.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdSetChannel, attrs).await
+    }
+
+    /// Change the settings of a wiphy
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{WiphyId, WiphyParams, AsyncSocket};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let params = WiphyParams::new().frag_threshold(u32::MAX).coverage_class(2);
+    /// AsyncSocket::connect()?.set_wiphy(WiphyId::Phy(0), &params).await?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn set_wiphy(&mut self, wiphy: WiphyId, params: &WiphyParams) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        wiphy.append_attrs(&mut attrs)?;
+        params.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdSetWiphy, attrs).await
+    }
+
+    /// Set the transmit power of a wiphy, or of a single interface if supported by the driver
+    ///
+    /// `mbm` is the fixed level or the limit in mBm, it is ignored with
+    /// [`Nl80211TxPowerSetting::TxPowerAutomatic`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{Nl80211TxPowerSetting, WiphyId, AsyncSocket};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let mut socket = AsyncSocket::connect()?;
+    /// for dbm in 5..=20 {
+    ///     let setting = Nl80211TxPowerSetting::TxPowerFixed;
+    ///     socket.set_tx_power(WiphyId::Interface(3), setting, dbm * 100).await?;
+    /// }
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn set_tx_power(
+        &mut self,
+        wiphy: WiphyId,
+        setting: Nl80211TxPowerSetting,
+        mbm: i32,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        wiphy.append_attrs(&mut attrs)?;
+        attrs.push(new_attr(
+            Nl80211Attr::AttrWiphyTxPowerSetting,
+            u32::from(u16::from(setting)),
+        )?);
+        if setting != Nl80211TxPowerSetting::TxPowerAutomatic {
+            attrs.push(new_attr(Nl80211Attr::AttrWiphyTxPowerLevel, mbm)?);
+        }
+        self.send_cmd(Nl80211Cmd::CmdSetWiphy, attrs).await
+    }
+
+    /// Get the power save state of an interface
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{Nl80211PsState, AsyncSocket};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let mut socket = AsyncSocket::connect()?;
+    /// if socket.get_power_save(3).await? == Nl80211PsState::PsEnabled {
+    ///     socket.set_power_save(3, Nl80211PsState::PsDisabled).await?;
+    /// }
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn get_power_save(
+        &mut self,
+        interface_index: i32,
+    ) -> Result<Nl80211PsState, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        match self
+            .request(Nl80211Cmd::CmdGetPowerSave, attrs, false)
+            .await?
+            .first()
+        {
+            Some(msg) => {
+                let state: u32 = msg
+                    .get_attr_handle()
+                    .get_attr_payload_as(Nl80211Attr::AttrPsState)?;
+                Ok(Nl80211PsState::from(state as u16))
+            }
+            None => Err(NlError::msg("No response received")),
+        }
+    }
+
+    /// Enable or disable power save on an interface
+    pub async fn set_power_save(
+        &mut self,
+        interface_index: i32,
+        state: Nl80211PsState,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(
+            Nl80211Attr::AttrPsState,
+            u32::from(u16::from(state)),
+        )?);
+        self.send_cmd(Nl80211Cmd::CmdSetPowerSave, attrs).await
+    }
+
+    /// Restrict the rates used to transmit on an interface
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{BandRates, BitrateMask, Nl80211Bandc, AsyncSocket};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let mask = BitrateMask::new().band(Nl80211Bandc::Band2ghz, BandRates::new().ht_mcs(&[7]));
+    /// AsyncSocket::connect()?.set_tx_bitrate_mask(3, &mask).await?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn set_tx_bitrate_mask(
+        &mut self,
+        interface_index: i32,
+        mask: &BitrateMask,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        mask.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdSetTxBitrateMask, attrs).await
+    }
+
+    /// Set the rate used to transmit multicast frames, in units of 100 kbps
+    pub async fn set_mcast_rate(&mut self, interface_index: i32, rate: u32) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrMcastRate, rate)?);
+        self.send_cmd(Nl80211Cmd::CmdSetMcastRate, attrs).await
+    }
+
+    /// Change the parameters of the BSS of an AP or mesh interface
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{BssParams, AsyncSocket};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let params = BssParams::new().cts_prot(true).basic_rates(&[12, 24, 48]);
+    /// AsyncSocket::connect()?.set_bss(3, &params).await?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn set_bss(
+        &mut self,
+        interface_index: i32,
+        params: &BssParams,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        params.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdSetBss, attrs).await
+    }
+
+    /// Start operating as an AP
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{ApSettings, BeaconData, AsyncSocket};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// # let (head, tail) = (vec![], vec![]);
+    /// let settings = ApSettings::new(b"portal", BeaconData::new(&head, &tail));
+    /// AsyncSocket::connect()?.start_ap(3, &settings).await?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn start_ap(
+        &mut self,
+        interface_index: i32,
+        settings: &ApSettings,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        settings.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdStartAp, attrs).await
+    }
+
+    /// Stop operating as an AP
+    pub async fn stop_ap(&mut self, interface_index: i32) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        self.send_cmd(Nl80211Cmd::CmdStopAp, attrs).await
+    }
+
+    /// Update the beacon and probe response templates of a running AP
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{BeaconBuilder, AsyncSocket};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let beacon = BeaconBuilder::new(&[0x02, 0, 0, 0, 1, 0], b"portal").ds_channel(6).build();
+    /// AsyncSocket::connect()?.set_beacon(3, &beacon).await?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn set_beacon(
+        &mut self,
+        interface_index: i32,
+        beacon: &BeaconData,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        beacon.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdSetBeacon, attrs).await
+    }
+
+    /// Announce a channel switch and move the interface to the new channel
+    ///
+    /// AP and P2P GO interfaces need a CSA beacon, cf. [`ChannelSwitchParams`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{BeaconBuilder, ChannelDef, ChannelSwitchParams, AsyncSocket};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let bssid = [0x02, 0, 0, 0, 1, 0];
+    /// let after = BeaconBuilder::new(&bssid, b"portal").build();
+    /// // Channel switch announcement element: mode, new channel and counter
+    /// let during = BeaconBuilder::new(&bssid, b"portal").tail_ies(&[0x25, 3, 1, 36, 5]).build();
+    /// let params = ChannelSwitchParams::new(ChannelDef::new(5180), 5)
+    ///     .beacon_after(after)
+    ///     .csa_beacon(during, &[4], &[]);
+    /// AsyncSocket::connect()?.channel_switch(3, &params).await?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn channel_switch(
+        &mut self,
+        interface_index: i32,
+        params: &ChannelSwitchParams,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        params.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdChannelSwitch, attrs).await
+    }
+
+    /// Start a channel availability check (CAC) on a DFS channel
+    ///
+    /// The duration of the check is set by the regulatory domain. The outcome is reported by
+    /// [`Event::Radar`](crate::Event::Radar) events.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{ChannelDef, Nl80211ChanWidth, AsyncSocket};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let channel = ChannelDef::new(5500).with_width(Nl80211ChanWidth::ChanWidth80, 5530);
+    /// AsyncSocket::connect()?.start_radar_detection(3, &channel).await?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn start_radar_detection(
+        &mut self,
+        interface_index: i32,
+        channel: &ChannelDef,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        channel.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdRadarDetect, attrs).await
+    }
+
+    /// Replace the MAC ACL of a running AP
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{MacAcl, Nl80211AclPolicy, AsyncSocket};
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let acl = MacAcl::new(Nl80211AclPolicy::AclPolicyAcceptUnlessListed)
+    ///     .mac(&[0x02, 0, 0, 0, 1, 0]);
+    /// AsyncSocket::connect()?.set_mac_acl(3, &acl).await?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn set_mac_acl(&mut self, interface_index: i32, acl: &MacAcl) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        acl.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdSetMacAcl, attrs).await
+    }
+
+    /// Get the maximum number of MAC addresses in an ACL, `None` if ACLs aren't supported
+    pub async fn get_mac_acl_max(&mut self, wiphy: WiphyId) -> Result<Option<u32>, NlError> {
+        let mut attrs = GenlBuffer::new();
+        wiphy.append_attrs(&mut attrs)?;
+        match self
+            .request(Nl80211Cmd::CmdGetWiphy, attrs, false)
+            .await?
+            .first()
+        {
+            Some(msg) => match msg
+                .get_attr_handle()
+                .get_attribute(Nl80211Attr::AttrMacAclMax)
+            {
+                Some(attr) => Ok(Some(attr.get_payload_as()?)),
+                None => Ok(None),
+            },
+            None => Err(NlError::msg("No response received")),
+        }
+    }
+
+    /// Send a null data frame to a station associated to an AP and wait for its TX status
+    ///
+    /// Waits for up to 5 seconds, an error being returned if no TX status is reported in time.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::AsyncSocket;
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let status = AsyncSocket::connect()?.probe_client(3, &[0x02, 0, 0, 0, 1, 0]).await?;
+    /// println!("station present: {}", status.acked);
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn probe_client(
+        &mut self,
+        interface_index: i32,
+        mac: &[u8],
+    ) -> Result<ProbeStatus, NlError> {
+        let cmd = Nl80211Cmd::CmdProbeClient;
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrMac, mac)?);
+
+        // Subscribe before sending the command so that the status cannot be missed
+        let mut events = Self::try_from(Socket::subscribe("mlme")?)?;
+        let cookie: u64 = match self.request(cmd, attrs, false).await?.first() {
+            Some(msg) => msg
+                .get_attr_handle()
+                .get_attr_payload_as(Nl80211Attr::AttrCookie)?,
+            None => return Err(NlError::msg("No response received")),
+        };
+
+        let deadline = Instant::now() + EVENT_TIMEOUT;
+        let mut buf = Vec::new();
+
+        loop {
+            let recv = events
+                .sock
+                .recv::<u16, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>(&mut buf);
+            let res = match timeout_at(deadline, recv).await {
+                Ok(res) => res?,
+                Err(_) => return Err(NlError::msg("No TX status received")),
+            };
+            for response in res {
+                if let NlPayload::Payload(msg) = response.nl_payload {
+                    if is_reply_to(&msg, cmd, interface_index) {
+                        let status: ProbeStatus = msg.get_attr_handle().try_into()?;
+                        if status.cookie == Some(cookie) {
+                            return Ok(status);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Get the regulatory domain of a wiphy, or the global one if `wiphy` is `None`
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::AsyncSocket;
+    /// # use std::error::Error;
+    /// # async fn test() -> Result<(), Box<dyn Error>> {
+    /// let domain = AsyncSocket::connect()?.get_regulatory(None).await?;
+    /// println!("{:?}", domain.alpha2);
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub async fn get_regulatory(&mut self, wiphy: Option<u32>) -> Result<RegDomain, NlError> {
+        let mut attrs = GenlBuffer::new();
+        if let Some(wiphy) = wiphy {
+            attrs.push(new_attr(Nl80211Attr::AttrWiphy, wiphy)?);
+        }
+        self.get_info(Nl80211Cmd::CmdGetReg, attrs).await
+    }
+
+    /// Get the global regulatory domain and the ones of the wiphys having their own
+    pub async fn get_regulatory_domains(&mut self) -> Result<Vec<RegDomain>, NlError> {
+        self.get_info_vec(Nl80211Cmd::CmdGetReg, GenlBuffer::new())
             .await
     }
 }
@@ -1,11 +1,38 @@
 use neli::attr::AttrHandle;
 use neli::consts::genl::NlAttrType;
+use neli::err::SerError;
 use neli::genl::Nlattr;
 use neli::types::{Buffer, GenlBuffer};
+use neli::{Size, ToBytes};
 use neli_proc_macros::neli_enum;
 
 pub type Attrs<'a, T> = AttrHandle<'a, GenlBuffer<T, Buffer>, Nlattr<T, Buffer>>;
 
+/// Create an attribute for a request
+pub(crate) fn new_attr<T, P>(nla_type: T, payload: P) -> Result<Nlattr<T, Buffer>, SerError>
+where
+    T: NlAttrType,
+    P: Size + ToBytes,
+{
+    Nlattr::new(false, false, nla_type, payload)
+}
+
+/// Create a nested attribute for a request
+pub(crate) fn new_nested_attr<T, TT>(
+    nla_type: T,
+    attrs: &[Nlattr<TT, Buffer>],
+) -> Result<Nlattr<T, Buffer>, SerError>
+where
+    T: NlAttrType,
+    TT: NlAttrType,
+{
+    let mut nested = Nlattr::new(true, false, nla_type, Buffer::new())?;
+    for attr in attrs {
+        nested.add_nested_attribute(attr)?;
+    }
+    Ok(nested)
+}
+
 #[neli_enum(serialized_type = "u16")]
 pub enum NlaNested {
     Unspec = 0,
@@ -260,6 +287,81 @@ pub enum Nl80211Attr {
     AttrNanDual = 239,
     AttrNanFunc = 240,
     AttrNanMatch = 241,
+    AttrFilsKek = 242,
+    AttrFilsNonces = 243,
+    AttrMulticastToUnicastEnabled = 244,
+    AttrBssid = 245,
+    AttrSchedScanRelativeRssi = 246,
+    AttrSchedScanRssiAdjust = 247,
+    AttrTimeoutReason = 248,
+    AttrFilsErpUsername = 249,
+    AttrFilsErpRealm = 250,
+    AttrFilsErpNextSeqNum = 251,
+    AttrFilsErpRrk = 252,
+    AttrFilsCacheId = 253,
+    AttrPmk = 254,
+    AttrSchedScanMulti = 255,
+    AttrSchedScanMaxReqs = 256,
+    AttrWant1x4wayHs = 257,
+    AttrPmkr0Name = 258,
+    AttrPortAuthorized = 259,
+    AttrExternalAuthAction = 260,
+    AttrExternalAuthSupport = 261,
+    AttrNss = 262,
+    AttrAckSignal = 263,
+    AttrControlPortOverNl80211 = 264,
+    AttrTxqStats = 265,
+    AttrTxqLimit = 266,
+    AttrTxqMemoryLimit = 267,
+    AttrTxqQuantum = 268,
+    AttrHeCapability = 269,
+    AttrFtmResponder = 270,
+    AttrFtmResponderStats = 271,
+    AttrTimeout = 272,
+    AttrPeerMeasurements = 273,
+    AttrAirtimeWeight = 274,
+    AttrStaTxPowerSetting = 275,
+    AttrStaTxPower = 276,
+    AttrSaePassword = 277,
+    AttrTwtResponder = 278,
+    AttrHeObssPd = 279,
+    AttrWiphyEdmgChannels = 280,
+    AttrWiphyEdmgBwConfig = 281,
+    AttrVlanId = 282,
+    AttrHeBssColor = 283,
+    AttrIftypeAkmSuites = 284,
+    AttrTidConfig = 285,
+    AttrControlPortNoPreauth = 286,
+    AttrPmkLifetime = 287,
+    AttrPmkReauthThreshold = 288,
+    AttrReceiveMulticast = 289,
+    AttrWiphyFreqOffset = 290,
+    AttrCenterFreq1Offset = 291,
+    AttrScanFreqKhz = 292,
+    AttrHe6ghzCapability = 293,
+    AttrFilsDiscovery = 294,
+    AttrUnsolBcastProbeResp = 295,
+    AttrS1gCapability = 296,
+    AttrS1gCapabilityMask = 297,
+    AttrSaePwe = 298,
+    AttrReconnectRequested = 299,
+    AttrSarSpec = 300,
+    AttrDisableHe = 301,
+    AttrObssColorBitmap = 302,
+    AttrColorChangeCount = 303,
+    AttrColorChangeColor = 304,
+    AttrColorChangeElems = 305,
+    AttrMbssidConfig = 306,
+    AttrMbssidElems = 307,
+    AttrRadarBackground = 308,
+    AttrApSettingsFlags = 309,
+    AttrEhtCapability = 310,
+    AttrDisableEht = 311,
+    AttrMloLinks = 312,
+    AttrMloLinkId = 313,
+    AttrMldAddr = 314,
+    AttrMloSupport = 315,
+    AttrMaxNumAkmSuites = 316,
 }
 
 impl NlAttrType for Nl80211Attr {}
@@ -421,7 +523,7 @@ pub enum Nl80211MpathFlags {
 
 impl NlAttrType for Nl80211MpathFlags {}
 
-/// nl80211MpathFlags
+/// nl80211MpathInfo
 ///
 /// Enumeration from nl80211/nl80211.h:2697
 #[neli_enum(serialized_type = "u16")]
@@ -434,6 +536,8 @@ pub enum Nl80211MpathInfo {
     MpathInfoFlags = 5,
     MpathInfoDiscoveryTimeout = 6,
     MpathInfoDiscoveryRetries = 7,
+    MpathInfoHopCount = 8,
+    MpathInfoPathChange = 9,
 }
 
 impl NlAttrType for Nl80211MpathInfo {}
@@ -553,7 +657,7 @@ impl NlAttrType for Nl80211SchedScanMatchAttr {}
 /// nl80211RegRuleFlags
 ///
 /// Enumeration from nl80211/nl80211.h:3026
-#[neli_enum(serialized_type = "u16")]
+#[neli_enum(serialized_type = "u32")]
 pub enum Nl80211RegRuleFlags {
     RrfNoOfdm = 1 << 0,
     RrfNoCck = 1 << 1,
@@ -568,11 +672,12 @@ pub enum Nl80211RegRuleFlags {
     RrfIrConcurrent = 1 << 12,
     RrfNoHt40minus = 1 << 13,
     RrfNoHt40plus = 1 << 14,
-    RrfNo80mhz = 1 << 15, // RrfNo160mhz  =1 << 16
+    RrfNo80mhz = 1 << 15,
+    RrfNo160mhz = 1 << 16,
+    RrfNoHe = 1 << 17,
+    RrfNo320mhz = 1 << 18,
 }
 
-impl NlAttrType for Nl80211RegRuleFlags {}
-
 /// nl80211DfsRegions
 ///
 /// Enumeration from nl80211/nl80211.h:3061
@@ -823,9 +928,12 @@ pub enum Nl80211AuthType {
     AuthtypeFt = 2,
     AuthtypeNetworkEap = 3,
     AuthtypeSae = 4,
-    AuthtypeNum = 5,
-    AuthtypeMax = 4,
-    AuthtypeAutomatic = 5,
+    AuthtypeFilsSk = 5,
+    AuthtypeFilsSkPfs = 6,
+    AuthtypeFilsPk = 7,
+    AuthtypeNum = 8,
+    AuthtypeMax = 7,
+    AuthtypeAutomatic = 8,
 }
 
 impl NlAttrType for Nl80211AuthType {}
@@ -849,6 +957,7 @@ impl NlAttrType for Nl80211KeyType {}
 pub enum Nl80211Mfp {
     MfpNo = 0,
     MfpRequired = 1,
+    MfpOptional = 2,
 }
 
 impl NlAttrType for Nl80211Mfp {}
@@ -860,6 +969,7 @@ impl NlAttrType for Nl80211Mfp {}
 pub enum Nl80211WpaVersions {
     WpaVersion1 = 1 << 0,
     WpaVersion2 = 1 << 1,
+    WpaVersion3 = 1 << 2,
 }
 
 impl NlAttrType for Nl80211WpaVersions {}
@@ -890,6 +1000,8 @@ pub enum Nl80211KeyAttributes {
     KeyDefaultMgmt = 6,
     KeyType = 7,
     KeyDefaultTypes = 8,
+    KeyMode = 9,
+    KeyDefaultBeacon = 10,
 }
 
 impl NlAttrType for Nl80211KeyAttributes {}
@@ -904,6 +1016,9 @@ pub enum Nl80211TxRateAttributes {
     TxrateHt = 2,
     TxrateVht = 3,
     TxrateGi = 4,
+    TxrateHe = 5,
+    TxrateHeGi = 6,
+    TxrateHeLtf = 7,
 }
 
 impl NlAttrType for Nl80211TxRateAttributes {}
@@ -928,6 +1043,9 @@ pub enum Nl80211Bandc {
     Band2ghz = 0,
     Band5ghz = 1,
     Band60ghz = 2,
+    Band6ghz = 3,
+    BandS1ghz = 4,
+    BandLc = 5,
 }
 
 impl NlAttrType for Nl80211Bandc {}
@@ -1137,6 +1255,7 @@ pub enum Nl80211RekeyData {
     RekeyDataKek = 1,
     RekeyDataKck = 2,
     RekeyDataReplayCtr = 3,
+    RekeyDataAkm = 4,
 }
 
 impl NlAttrType for Nl80211RekeyData {}
@@ -1319,6 +1438,8 @@ pub enum Nl80211RadarEvent {
     RadarCacFinished = 1,
     RadarCacAborted = 2,
     RadarNopFinished = 3,
+    RadarPreCacExpired = 4,
+    RadarCacStarted = 5,
 }
 
 impl NlAttrType for Nl80211RadarEvent {}
@@ -1505,3 +1626,24 @@ pub enum Nl80211NanMatchAttributes {
 }
 
 impl NlAttrType for Nl80211NanMatchAttributes {}
+
+/// nl80211TxqStats
+///
+/// Enumeration from nl80211/nl80211.h
+#[neli_enum(serialized_type = "u16")]
+pub enum Nl80211TxqStats {
+    TxqStatsInvalid = 0,
+    TxqStatsBacklogBytes = 1,
+    TxqStatsBacklogPackets = 2,
+    TxqStatsFlows = 3,
+    TxqStatsDrops = 4,
+    TxqStatsEcnMarks = 5,
+    TxqStatsOverlimit = 6,
+    TxqStatsOvermemory = 7,
+    TxqStatsCollisions = 8,
+    TxqStatsTxBytes = 9,
+    TxqStatsTxPackets = 10,
+    TxqStatsMaxFlows = 11,
+}
+
+impl NlAttrType for Nl80211TxqStats {}
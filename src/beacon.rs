@@ -0,0 +1,343 @@
+use crate::ap::BeaconData;
+
+const ELEM_SSID: u8 = 0;
+const ELEM_SUPP_RATES: u8 = 1;
+const ELEM_DS_PARAMS: u8 = 3;
+const ELEM_COUNTRY: u8 = 7;
+const ELEM_HT_CAPA: u8 = 45;
+const ELEM_RSN: u8 = 48;
+const ELEM_EXT_SUPP_RATES: u8 = 50;
+const ELEM_HT_OPER: u8 = 61;
+const ELEM_VHT_CAPA: u8 = 191;
+const ELEM_VHT_OPER: u8 = 192;
+const ELEM_EXTENSION: u8 = 255;
+const ELEM_EXT_HE_CAPA: u8 = 35;
+const ELEM_EXT_HE_OPER: u8 = 36;
+
+const CAPA_ESS: u16 = 0x0001;
+const CAPA_PRIVACY: u16 = 0x0010;
+const CAPA_SHORT_PREAMBLE: u16 = 0x0020;
+const CAPA_SHORT_SLOT_TIME: u16 = 0x0400;
+
+/// Builder of the beacon template of an AP
+///
+/// The elements are laid out in the order of the standard, with the TIM element left out for the
+/// kernel to insert between the head and the tail. Cipher and AKM suites are given as selectors,
+/// e.g. `0x000fac04` for CCMP, and rates in units of 500 kbps.
+///
+/// # Example
+///
+/// ```
+/// # use neli_wifi::BeaconBuilder;
+/// let beacon = BeaconBuilder::new(&[0x02, 0, 0, 0, 1, 0], b"portal")
+///     .rates(&[2, 4, 11, 22, 12, 18, 24, 36, 48, 72, 96, 108], &[2, 4, 11, 22])
+///     .ds_channel(6)
+///     .country(b"FR", 0x20, &[(1, 13, 20)])
+///     .rsn(0x000fac04, &[0x000fac04], &[0x000fac02], 0)
+///     .build();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BeaconBuilder {
+    bssid: Vec<u8>,
+    ssid: Vec<u8>,
+    beacon_interval: u16,
+    capability: u16,
+    rates: Vec<u8>,
+    ds_channel: Option<u8>,
+    country: Option<Vec<u8>>,
+    rsn: Option<Vec<u8>>,
+    ht_capa: Option<Vec<u8>>,
+    ht_oper: Option<Vec<u8>>,
+    vht_capa: Option<Vec<u8>>,
+    vht_oper: Option<Vec<u8>>,
+    he_capa: Option<Vec<u8>>,
+    he_oper: Option<Vec<u8>>,
+    tail_ies: Vec<u8>,
+}
+
+impl BeaconBuilder {
+    /// Beacon of the ESS `ssid` sent by `bssid` every 100 TU
+    pub fn new(bssid: &[u8], ssid: &[u8]) -> Self {
+        Self {
+            bssid: bssid.to_vec(),
+            ssid: ssid.to_vec(),
+            beacon_interval: 100,
+            capability: CAPA_ESS,
+            rates: Vec::new(),
+            ds_channel: None,
+            country: None,
+            rsn: None,
+            ht_capa: None,
+            ht_oper: None,
+            vht_capa: None,
+            vht_oper: None,
+            he_capa: None,
+            he_oper: None,
+            tail_ies: Vec::new(),
+        }
+    }
+
+    /// Beacon interval in TU
+    pub fn beacon_interval(mut self, interval: u16) -> Self {
+        self.beacon_interval = interval;
+        self
+    }
+
+    /// Hide the SSID, the element is then sent empty
+    pub fn hidden_ssid(mut self) -> Self {
+        self.ssid.clear();
+        self
+    }
+
+    /// Advertise that data frames are protected
+    pub fn privacy(mut self, privacy: bool) -> Self {
+        self.set_capability(CAPA_PRIVACY, privacy);
+        self
+    }
+
+    /// Advertise the use of short preambles
+    pub fn short_preamble(mut self, short_preamble: bool) -> Self {
+        self.set_capability(CAPA_SHORT_PREAMBLE, short_preamble);
+        self
+    }
+
+    /// Advertise the use of short slot times
+    pub fn short_slot_time(mut self, short_slot_time: bool) -> Self {
+        self.set_capability(CAPA_SHORT_SLOT_TIME, short_slot_time);
+        self
+    }
+
+    /// Supported rates, the ones in `basic` are marked as part of the basic rate set
+    ///
+    /// Rates beyond the eighth one go to the extended supported rates element.
+    pub fn rates(mut self, supported: &[u8], basic: &[u8]) -> Self {
+        self.rates = supported
+            .iter()
+            .map(|&rate| match basic.contains(&rate) {
+                true => rate | 0x80,
+                false => rate,
+            })
+            .collect();
+        self
+    }
+
+    /// Channel number advertised in the DS parameter set element
+    pub fn ds_channel(mut self, channel: u8) -> Self {
+        self.ds_channel = Some(channel);
+        self
+    }
+
+    /// Country element, with the environment given as the third byte of the country string
+    /// (`0x20` for any, `b'I'` for indoor and `b'O'` for outdoor) and the subbands as first
+    /// channel, number of channels and maximum transmit power in dBm
+    pub fn country(mut self, alpha2: &[u8; 2], environment: u8, subbands: &[(u8, u8, i8)]) -> Self {
+        let mut country = vec![alpha2[0], alpha2[1], environment];
+        for &(first_channel, num_channels, max_power) in subbands {
+            country.extend_from_slice(&[first_channel, num_channels, max_power as u8]);
+        }
+        if country.len() % 2 == 1 {
+            country.push(0);
+        }
+        self.country = Some(country);
+        self
+    }
+
+    /// RSN element advertising the given suites and RSN capabilities
+    pub fn rsn(
+        mut self,
+        group_cipher: u32,
+        pairwise_ciphers: &[u32],
+        akm_suites: &[u32],
+        capabilities: u16,
+    ) -> Self {
+        let mut rsn = 1u16.to_le_bytes().to_vec();
+        rsn.extend_from_slice(&group_cipher.to_be_bytes());
+        for suites in [pairwise_ciphers, akm_suites] {
+            rsn.extend_from_slice(&(suites.len() as u16).to_le_bytes());
+            for suite in suites {
+                rsn.extend_from_slice(&suite.to_be_bytes());
+            }
+        }
+        rsn.extend_from_slice(&capabilities.to_le_bytes());
+        self.rsn = Some(rsn);
+        self
+    }
+
+    /// HT capabilities and HT operation elements, given without their headers
+    pub fn ht(mut self, capabilities: &[u8], operation: &[u8]) -> Self {
+        self.ht_capa = Some(capabilities.to_vec());
+        self.ht_oper = Some(operation.to_vec());
+        self
+    }
+
+    /// VHT capabilities and VHT operation elements, given without their headers
+    pub fn vht(mut self, capabilities: &[u8], operation: &[u8]) -> Self {
+        self.vht_capa = Some(capabilities.to_vec());
+        self.vht_oper = Some(operation.to_vec());
+        self
+    }
+
+    /// HE capabilities and HE operation elements, given without their headers and element ID
+    /// extensions
+    pub fn he(mut self, capabilities: &[u8], operation: &[u8]) -> Self {
+        self.he_capa = Some(capabilities.to_vec());
+        self.he_oper = Some(operation.to_vec());
+        self
+    }
+
+    /// Raw elements appended to the tail, e.g. vendor specific ones
+    pub fn tail_ies(mut self, ies: &[u8]) -> Self {
+        self.tail_ies.extend_from_slice(ies);
+        self
+    }
+
+    /// Head and tail of the beacon
+    pub fn build(&self) -> BeaconData {
+        let mut head = vec![0x80, 0x00, 0x00, 0x00];
+        head.extend_from_slice(&[0xff; 6]);
+        head.extend_from_slice(&self.bssid);
+        head.extend_from_slice(&self.bssid);
+        head.extend_from_slice(&[0; 2 + 8]);
+        head.extend_from_slice(&self.beacon_interval.to_le_bytes());
+        head.extend_from_slice(&self.capability.to_le_bytes());
+        push_elem(&mut head, ELEM_SSID, &self.ssid);
+        let (rates, ext_rates) = self.rates.split_at(self.rates.len().min(8));
+        if !rates.is_empty() {
+            push_elem(&mut head, ELEM_SUPP_RATES, rates);
+        }
+        if let Some(channel) = self.ds_channel {
+            push_elem(&mut head, ELEM_DS_PARAMS, &[channel]);
+        }
+
+        let mut tail = Vec::new();
+        if let Some(country) = &self.country {
+            push_elem(&mut tail, ELEM_COUNTRY, country);
+        }
+        if !ext_rates.is_empty() {
+            push_elem(&mut tail, ELEM_EXT_SUPP_RATES, ext_rates);
+        }
+        if let Some(rsn) = &self.rsn {
+            push_elem(&mut tail, ELEM_RSN, rsn);
+        }
+        if let Some(capa) = &self.ht_capa {
+            push_elem(&mut tail, ELEM_HT_CAPA, capa);
+        }
+        if let Some(oper) = &self.ht_oper {
+            push_elem(&mut tail, ELEM_HT_OPER, oper);
+        }
+        if let Some(capa) = &self.vht_capa {
+            push_elem(&mut tail, ELEM_VHT_CAPA, capa);
+        }
+        if let Some(oper) = &self.vht_oper {
+            push_elem(&mut tail, ELEM_VHT_OPER, oper);
+        }
+        if let Some(capa) = &self.he_capa {
+            push_ext_elem(&mut tail, ELEM_EXT_HE_CAPA, capa);
+        }
+        if let Some(oper) = &self.he_oper {
+            push_ext_elem(&mut tail, ELEM_EXT_HE_OPER, oper);
+        }
+        tail.extend_from_slice(&self.tail_ies);
+
+        BeaconData::new(&head, &tail)
+    }
+
+    fn set_capability(&mut self, bit: u16, set: bool) {
+        match set {
+            true => self.capability |= bit,
+            false => self.capability &= !bit,
+        }
+    }
+}
+
+fn push_elem(buf: &mut Vec<u8>, id: u8, body: &[u8]) {
+    buf.push(id);
+    buf.push(body.len() as u8);
+    buf.extend_from_slice(body);
+}
+
+fn push_ext_elem(buf: &mut Vec<u8>, ext_id: u8, body: &[u8]) {
+    buf.push(ELEM_EXTENSION);
+    buf.push(body.len() as u8 + 1);
+    buf.push(ext_id);
+    buf.extend_from_slice(body);
+}
+
+#[cfg(test)]
+mod test_beacon {
+    use super::*;
+
+    const BSSID: [u8; 6] = [0x02, 0x00, 0x00, 0x00, 0x01, 0x00];
+
+    #[test]
+    fn test_open_beacon() {
+        let beacon = BeaconBuilder::new(&BSSID, b"test")
+            .rates(&[2, 4, 11, 22], &[2, 4])
+            .ds_channel(1)
+            .build();
+
+        let head = vec![
+            0x80, 0x00, 0x00, 0x00, // frame control, duration
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // destination
+            0x02, 0x00, 0x00, 0x00, 0x01, 0x00, // source
+            0x02, 0x00, 0x00, 0x00, 0x01, 0x00, // BSSID
+            0x00, 0x00, // sequence control
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // timestamp
+            0x64, 0x00, // beacon interval
+            0x01, 0x00, // capabilities
+            0x00, 0x04, b't', b'e', b's', b't', // SSID
+            0x01, 0x04, 0x82, 0x84, 0x0b, 0x16, // supported rates
+            0x03, 0x01, 0x01, // DS parameter set
+        ];
+        assert_eq!(beacon.head(), head.as_slice());
+        assert!(beacon.tail().is_empty());
+    }
+
+    #[test]
+    fn test_rsn_beacon() {
+        let beacon = BeaconBuilder::new(&BSSID, b"test")
+            .beacon_interval(200)
+            .privacy(true)
+            .short_slot_time(true)
+            .rates(
+                &[2, 4, 11, 22, 12, 18, 24, 36, 48, 72, 96, 108],
+                &[2, 4, 11, 22],
+            )
+            .country(b"FR", 0x20, &[(1, 13, 20)])
+            .rsn(0x000fac04, &[0x000fac04], &[0x000fac02], 0x000c)
+            .ht(&[0xee; 26], &[0x01; 22])
+            .he(&[0xaa; 2], &[0xbb; 3])
+            .tail_ies(&[0xdd, 0x01, 0x00])
+            .build();
+
+        assert_eq!(
+            &beacon.head()[32..],
+            &[
+                0xc8, 0x00, // beacon interval
+                0x11, 0x04, // capabilities
+                0x00, 0x04, b't', b'e', b's', b't', // SSID
+                0x01, 0x08, 0x82, 0x84, 0x8b, 0x96, 0x0c, 0x12, 0x18, 0x24, // supported rates
+            ]
+        );
+
+        let mut tail = vec![
+            0x07, 0x06, b'F', b'R', 0x20, 0x01, 0x0d, 0x14, // country
+            0x32, 0x04, 0x30, 0x48, 0x60, 0x6c, // extended supported rates
+            0x30, 0x14, 0x01, 0x00, // RSN, version
+            0x00, 0x0f, 0xac, 0x04, // group cipher
+            0x01, 0x00, 0x00, 0x0f, 0xac, 0x04, // pairwise ciphers
+            0x01, 0x00, 0x00, 0x0f, 0xac, 0x02, // AKM suites
+            0x0c, 0x00, // RSN capabilities
+            0x2d, 0x1a, // HT capabilities
+        ];
+        tail.extend_from_slice(&[0xee; 26]);
+        tail.extend_from_slice(&[0x3d, 0x16]);
+        tail.extend_from_slice(&[0x01; 22]);
+        tail.extend_from_slice(&[
+            0xff, 0x03, 0x23, 0xaa, 0xaa, // HE capabilities
+            0xff, 0x04, 0x24, 0xbb, 0xbb, 0xbb, // HE operation
+            0xdd, 0x01, 0x00, // vendor specific
+        ]);
+        assert_eq!(beacon.tail(), tail.as_slice());
+    }
+}
@@ -0,0 +1,191 @@
+use crate::attr::{
+    new_attr, new_nested_attr, Nl80211Attr, Nl80211Bandc, Nl80211TxRateAttributes, Nl80211TxrateGi,
+};
+
+use neli::err::SerError;
+use neli::types::{Buffer, GenlBuffer};
+
+/// Rates allowed to transmit on a band
+///
+/// Rates of a kind that isn't set are left to the driver, an empty set disables all of them.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BandRates {
+    legacy: Option<Vec<u8>>,
+    ht_mcs: Option<Vec<u8>>,
+    vht_mcs: Option<Vec<u16>>,
+    he_mcs: Option<Vec<u16>>,
+    gi: Option<Nl80211TxrateGi>,
+}
+
+impl BandRates {
+    /// No restriction
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Legacy rates in units of 500 kbps
+    pub fn legacy(mut self, rates: &[u8]) -> Self {
+        self.legacy = Some(rates.to_vec());
+        self
+    }
+
+    /// HT MCS indices
+    pub fn ht_mcs(mut self, mcs: &[u8]) -> Self {
+        self.ht_mcs = Some(mcs.to_vec());
+        self
+    }
+
+    /// Bitmaps of the VHT MCS allowed for each number of spatial streams
+    ///
+    /// Up to 8 spatial streams are supported, longer lists are rejected when sending the mask.
+    pub fn vht_mcs(mut self, mcs: &[u16]) -> Self {
+        self.vht_mcs = Some(mcs.to_vec());
+        self
+    }
+
+    /// Bitmaps of the HE MCS allowed for each number of spatial streams
+    ///
+    /// Up to 8 spatial streams are supported, longer lists are rejected when sending the mask.
+    pub fn he_mcs(mut self, mcs: &[u16]) -> Self {
+        self.he_mcs = Some(mcs.to_vec());
+        self
+    }
+
+    /// Guard interval to use with HT and VHT rates
+    pub fn gi(mut self, gi: Nl80211TxrateGi) -> Self {
+        self.gi = Some(gi);
+        self
+    }
+}
+
+/// Per band masks of the rates allowed to transmit
+///
+/// # Example
+///
+/// ```
+/// # use neli_wifi::{BandRates, BitrateMask, Nl80211Bandc, Nl80211TxrateGi};
+/// let mask = BitrateMask::new()
+///     .band(Nl80211Bandc::Band2ghz, BandRates::new().legacy(&[12, 18, 24]).ht_mcs(&[]))
+///     .band(
+///         Nl80211Bandc::Band5ghz,
+///         BandRates::new().vht_mcs(&[0x3ff, 0x3ff]).gi(Nl80211TxrateGi::TxrateForceLgi),
+///     );
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BitrateMask {
+    bands: Vec<(Nl80211Bandc, BandRates)>,
+}
+
+impl BitrateMask {
+    /// No restriction
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restrict the rates allowed on `band`
+    pub fn band(mut self, band: Nl80211Bandc, rates: BandRates) -> Self {
+        self.bands.push((band, rates));
+        self
+    }
+
+    pub(crate) fn append_attrs(
+        &self,
+        attrs: &mut GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), SerError> {
+        let mut bands = Vec::new();
+        for (band, rates) in &self.bands {
+            let mut band_attrs = Vec::new();
+            if let Some(legacy) = &rates.legacy {
+                band_attrs.push(new_attr(
+                    Nl80211TxRateAttributes::TxrateLegacy,
+                    legacy.as_slice(),
+                )?);
+            }
+            if let Some(mcs) = &rates.ht_mcs {
+                band_attrs.push(new_attr(Nl80211TxRateAttributes::TxrateHt, mcs.as_slice())?);
+            }
+            if let Some(mcs) = &rates.vht_mcs {
+                band_attrs.push(new_attr(
+                    Nl80211TxRateAttributes::TxrateVht,
+                    mcs_map(mcs)?.as_slice(),
+                )?);
+            }
+            if let Some(mcs) = &rates.he_mcs {
+                band_attrs.push(new_attr(
+                    Nl80211TxRateAttributes::TxrateHe,
+                    mcs_map(mcs)?.as_slice(),
+                )?);
+            }
+            if let Some(gi) = rates.gi {
+                band_attrs.push(new_attr(
+                    Nl80211TxRateAttributes::TxrateGi,
+                    u16::from(gi) as u8,
+                )?);
+            }
+            bands.push(new_nested_attr(*band, &band_attrs)?);
+        }
+        attrs.push(new_nested_attr(Nl80211Attr::AttrTxRates, &bands)?);
+        Ok(())
+    }
+}
+
+/// Layout of the MCS maps used by the kernel, one 16 bits bitmap for each of the 8 spatial streams
+fn mcs_map(mcs: &[u16]) -> Result<Vec<u8>, SerError> {
+    if mcs.len() > 8 {
+        return Err(SerError::new("MCS map with more than 8 spatial streams"));
+    }
+    Ok((0..8)
+        .flat_map(|nss| mcs.get(nss).copied().unwrap_or(0).to_ne_bytes())
+        .collect())
+}
+
+#[cfg(test)]
+mod test_bitrate_mask {
+    use super::*;
+    use neli::ToBytes;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_attrs() {
+        let mask = BitrateMask::new()
+            .band(
+                Nl80211Bandc::Band2ghz,
+                BandRates::new().legacy(&[12, 18, 24]).ht_mcs(&[]),
+            )
+            .band(
+                Nl80211Bandc::Band5ghz,
+                BandRates::new()
+                    .vht_mcs(&[0x3ff, 0x3ff])
+                    .he_mcs(&[0xff])
+                    .gi(Nl80211TxrateGi::TxrateForceLgi),
+            );
+        let mut attrs = GenlBuffer::new();
+        mask.append_attrs(&mut attrs).unwrap();
+        let mut bytes = Cursor::new(Vec::new());
+        attrs.to_bytes(&mut bytes).unwrap();
+
+        assert_eq!(
+            bytes.into_inner(),
+            vec![
+                72, 0, 90, 128, // TX rates
+                16, 0, 0, 128, // 2.4 GHz band
+                7, 0, 1, 0, 12, 18, 24, 0, // legacy rates
+                4, 0, 2, 0, // HT MCS
+                52, 0, 1, 128, // 5 GHz band
+                20, 0, 3, 0, 255, 3, 255, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, // VHT MCS
+                20, 0, 5, 0, 255, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, // HE MCS
+                5, 0, 4, 0, 2, 0, 0, 0, // guard interval
+            ]
+        );
+    }
+
+    #[test]
+    fn test_too_many_streams() {
+        let mask = BitrateMask::new().band(
+            Nl80211Bandc::Band5ghz,
+            BandRates::new().vht_mcs(&[0x3ff; 9]),
+        );
+
+        assert!(mask.append_attrs(&mut GenlBuffer::new()).is_err())
+    }
+}
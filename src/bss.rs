@@ -1,9 +1,10 @@
 use std::fmt;
 
-use crate::attr::{Attrs, Nl80211Attr, Nl80211Bss};
+use crate::attr::{new_attr, Attrs, Nl80211Attr, Nl80211Bss};
 
 use neli::attr::Attribute;
-use neli::err::DeError;
+use neli::err::{DeError, SerError};
+use neli::types::{Buffer, GenlBuffer};
 
 /// A struct representing a BSS (Basic Service Set)
 #[non_exhaustive]
@@ -77,6 +78,98 @@ impl fmt::Debug for Bss {
     }
 }
 
+/// Parameters of the BSS of an AP or mesh interface, only the ones that are set are changed
+///
+/// # Example
+///
+/// ```
+/// # use neli_wifi::BssParams;
+/// let params = BssParams::new().short_slot_time(true).ap_isolate(true);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BssParams {
+    cts_prot: Option<bool>,
+    short_preamble: Option<bool>,
+    short_slot_time: Option<bool>,
+    basic_rates: Option<Vec<u8>>,
+    ap_isolate: Option<bool>,
+    ht_opmode: Option<u16>,
+}
+
+impl BssParams {
+    /// No changes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use CTS protection
+    pub fn cts_prot(mut self, cts_prot: bool) -> Self {
+        self.cts_prot = Some(cts_prot);
+        self
+    }
+
+    /// Allow short preambles
+    pub fn short_preamble(mut self, short_preamble: bool) -> Self {
+        self.short_preamble = Some(short_preamble);
+        self
+    }
+
+    /// Use short slot time
+    pub fn short_slot_time(mut self, short_slot_time: bool) -> Self {
+        self.short_slot_time = Some(short_slot_time);
+        self
+    }
+
+    /// Basic rates in units of 500 kbps
+    pub fn basic_rates(mut self, rates: &[u8]) -> Self {
+        self.basic_rates = Some(rates.to_vec());
+        self
+    }
+
+    /// Do not forward frames between the stations of the BSS
+    pub fn ap_isolate(mut self, ap_isolate: bool) -> Self {
+        self.ap_isolate = Some(ap_isolate);
+        self
+    }
+
+    /// Operation mode field of the HT operation element
+    pub fn ht_opmode(mut self, ht_opmode: u16) -> Self {
+        self.ht_opmode = Some(ht_opmode);
+        self
+    }
+
+    pub(crate) fn append_attrs(
+        &self,
+        attrs: &mut GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), SerError> {
+        if let Some(cts_prot) = self.cts_prot {
+            attrs.push(new_attr(Nl80211Attr::AttrBssCtsProt, cts_prot as u8)?);
+        }
+        if let Some(short_preamble) = self.short_preamble {
+            attrs.push(new_attr(
+                Nl80211Attr::AttrBssShortPreamble,
+                short_preamble as u8,
+            )?);
+        }
+        if let Some(short_slot_time) = self.short_slot_time {
+            attrs.push(new_attr(
+                Nl80211Attr::AttrBssShortSlotTime,
+                short_slot_time as u8,
+            )?);
+        }
+        if let Some(rates) = &self.basic_rates {
+            attrs.push(new_attr(Nl80211Attr::AttrBssBasicRates, rates.as_slice())?);
+        }
+        if let Some(ap_isolate) = self.ap_isolate {
+            attrs.push(new_attr(Nl80211Attr::AttrApIsolate, ap_isolate as u8)?);
+        }
+        if let Some(ht_opmode) = self.ht_opmode {
+            attrs.push(new_attr(Nl80211Attr::AttrBssHtOpmode, ht_opmode)?);
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test_bss {
     use super::*;
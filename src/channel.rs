@@ -0,0 +1,131 @@
+use crate::attr::{new_attr, Attrs, Nl80211Attr, Nl80211ChanWidth, Nl80211ChannelType};
+
+use neli::attr::Attribute;
+use neli::err::{DeError, SerError};
+use neli::types::{Buffer, GenlBuffer};
+
+/// A struct representing a channel definition
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelDef {
+    /// Frequency of the control channel (MHz)
+    pub frequency: u32,
+    /// Channel width
+    pub width: Nl80211ChanWidth,
+    /// Center frequency of the whole channel (MHz)
+    pub center_freq1: Option<u32>,
+    /// Center frequency of the second segment of an 80+80 MHz channel (MHz)
+    pub center_freq2: Option<u32>,
+}
+
+impl ChannelDef {
+    /// A 20 MHz channel without HT
+    pub fn new(frequency: u32) -> Self {
+        Self {
+            frequency,
+            width: Nl80211ChanWidth::ChanWidth20Noht,
+            center_freq1: None,
+            center_freq2: None,
+        }
+    }
+
+    /// A 20 or 40 MHz channel described by its legacy channel type
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use neli_wifi::{ChannelDef, Nl80211ChanWidth, Nl80211ChannelType};
+    /// let channel = ChannelDef::from_channel_type(5180, Nl80211ChannelType::ChanHt40plus);
+    /// assert_eq!(channel.width, Nl80211ChanWidth::ChanWidth40);
+    /// assert_eq!(channel.center_freq1, Some(5190));
+    /// ```
+    pub fn from_channel_type(frequency: u32, channel_type: Nl80211ChannelType) -> Self {
+        match channel_type {
+            Nl80211ChannelType::ChanHt20 => {
+                Self::new(frequency).with_width(Nl80211ChanWidth::ChanWidth20, frequency)
+            }
+            Nl80211ChannelType::ChanHt40minus => {
+                Self::new(frequency).with_width(Nl80211ChanWidth::ChanWidth40, frequency - 10)
+            }
+            Nl80211ChannelType::ChanHt40plus => {
+                Self::new(frequency).with_width(Nl80211ChanWidth::ChanWidth40, frequency + 10)
+            }
+            _ => Self::new(frequency),
+        }
+    }
+
+    /// Set the channel width and the center frequency of the whole channel
+    pub fn with_width(mut self, width: Nl80211ChanWidth, center_freq1: u32) -> Self {
+        self.width = width;
+        self.center_freq1 = Some(center_freq1);
+        self
+    }
+
+    /// Set the center frequency of the second segment of an 80+80 MHz channel
+    pub fn with_center_freq2(mut self, center_freq2: u32) -> Self {
+        self.center_freq2 = Some(center_freq2);
+        self
+    }
+
+    pub(crate) fn append_attrs(
+        &self,
+        attrs: &mut GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), SerError> {
+        attrs.push(new_attr(Nl80211Attr::AttrWiphyFreq, self.frequency)?);
+        attrs.push(new_attr(
+            Nl80211Attr::AttrChannelWidth,
+            u32::from(u16::from(self.width)),
+        )?);
+        if let Some(center_freq1) = self.center_freq1 {
+            attrs.push(new_attr(Nl80211Attr::AttrCenterFreq1, center_freq1)?);
+        }
+        if let Some(center_freq2) = self.center_freq2 {
+            attrs.push(new_attr(Nl80211Attr::AttrCenterFreq2, center_freq2)?);
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<Attrs<'_, Nl80211Attr>> for ChannelDef {
+    type Error = DeError;
+
+    fn try_from(attrs: Attrs<'_, Nl80211Attr>) -> Result<Self, Self::Error> {
+        let mut frequency = None;
+        let mut res = Self::new(0);
+        for attr in attrs.iter() {
+            match attr.nla_type.nla_type {
+                Nl80211Attr::AttrWiphyFreq => frequency = Some(attr.get_payload_as()?),
+                Nl80211Attr::AttrChannelWidth => {
+                    res.width = Nl80211ChanWidth::from(attr.get_payload_as::<u32>()? as u16);
+                }
+                Nl80211Attr::AttrCenterFreq1 => res.center_freq1 = Some(attr.get_payload_as()?),
+                Nl80211Attr::AttrCenterFreq2 => res.center_freq2 = Some(attr.get_payload_as()?),
+                _ => (),
+            }
+        }
+        res.frequency = frequency.ok_or_else(|| DeError::new("Missing channel frequency"))?;
+        Ok(res)
+    }
+}
+
+/// Get the channel number of a frequency (MHz)
+///
+/// # Example
+///
+/// ```
+/// # use neli_wifi::frequency_to_channel;
+/// assert_eq!(frequency_to_channel(2412), Some(1));
+/// assert_eq!(frequency_to_channel(5180), Some(36));
+/// ```
+pub fn frequency_to_channel(frequency: u32) -> Option<u32> {
+    match frequency {
+        2484 => Some(14),
+        2412..=2483 => Some((frequency - 2407) / 5),
+        4910..=4980 => Some((frequency - 4000) / 5),
+        5000..=5924 => Some((frequency - 5000) / 5),
+        5935 => Some(2),
+        5950..=45000 => Some((frequency - 5950) / 5),
+        58320..=70200 => Some((frequency - 56160) / 2160),
+        _ => None,
+    }
+}
@@ -0,0 +1,233 @@
+use crate::attr::{
+    new_attr, new_nested_attr, Nl80211Attr, Nl80211AuthType, Nl80211Bandc, Nl80211BssSelectAttr,
+    Nl80211Mfp, Nl80211WpaVersions,
+};
+
+use neli::err::SerError;
+use neli::types::{Buffer, GenlBuffer};
+
+/// BSS selection behaviour used by drivers that select the BSS to connect to themselves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BssSelect {
+    /// Prefer the BSS with the highest RSSI
+    Rssi,
+    /// Prefer a BSS on the given band
+    BandPref(Nl80211Bandc),
+    /// Adjust the RSSI of BSSes on `band` by `delta` dB before comparing them
+    RssiAdjust { band: Nl80211Bandc, delta: i8 },
+}
+
+/// Parameters used to connect to a network with a driver that implements the SME
+///
+/// Cipher and AKM suites are given as selectors, i.e. the OUI followed by the suite type
+/// (`0x000fac04` is CCMP and `0x000fac02` is PSK).
+///
+/// # Example
+///
+/// ```
+/// # use neli_wifi::{ConnectParams, Nl80211WpaVersions};
+/// let params = ConnectParams::new(b"home")
+///     .wpa_versions(&[Nl80211WpaVersions::WpaVersion2])
+///     .pairwise_ciphers(&[0x000fac04])
+///     .group_cipher(0x000fac04)
+///     .akm_suites(&[0x000fac02])
+///     .psk(&[0; 32]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectParams {
+    ssid: Vec<u8>,
+    bssid: Option<Vec<u8>>,
+    bssid_hint: Option<Vec<u8>>,
+    frequency: Option<u32>,
+    frequency_hint: Option<u32>,
+    auth_type: Option<Nl80211AuthType>,
+    wpa_versions: Option<u32>,
+    pairwise_ciphers: Option<Vec<u32>>,
+    group_cipher: Option<u32>,
+    akm_suites: Option<Vec<u32>>,
+    psk: Option<Vec<u8>>,
+    sae_password: Option<Vec<u8>>,
+    mfp: Option<Nl80211Mfp>,
+    ie: Option<Vec<u8>>,
+    bss_select: Option<BssSelect>,
+}
+
+impl ConnectParams {
+    /// Connect to the network identified by `ssid`
+    pub fn new(ssid: &[u8]) -> Self {
+        Self {
+            ssid: ssid.to_vec(),
+            bssid: None,
+            bssid_hint: None,
+            frequency: None,
+            frequency_hint: None,
+            auth_type: None,
+            wpa_versions: None,
+            pairwise_ciphers: None,
+            group_cipher: None,
+            akm_suites: None,
+            psk: None,
+            sae_password: None,
+            mfp: None,
+            ie: None,
+            bss_select: None,
+        }
+    }
+
+    /// Only connect to the BSS with the given BSSID
+    pub fn bssid(mut self, bssid: &[u8]) -> Self {
+        self.bssid = Some(bssid.to_vec());
+        self
+    }
+
+    /// Prefer the BSS with the given BSSID, but allow the driver to choose another one
+    pub fn bssid_hint(mut self, bssid: &[u8]) -> Self {
+        self.bssid_hint = Some(bssid.to_vec());
+        self
+    }
+
+    /// Only connect to a BSS on the given frequency (MHz)
+    pub fn frequency(mut self, frequency: u32) -> Self {
+        self.frequency = Some(frequency);
+        self
+    }
+
+    /// Prefer a BSS on the given frequency (MHz)
+    pub fn frequency_hint(mut self, frequency: u32) -> Self {
+        self.frequency_hint = Some(frequency);
+        self
+    }
+
+    /// Authentication type, the driver picks one if not set
+    pub fn auth_type(mut self, auth_type: Nl80211AuthType) -> Self {
+        self.auth_type = Some(auth_type);
+        self
+    }
+
+    /// Allowed WPA versions
+    pub fn wpa_versions(mut self, versions: &[Nl80211WpaVersions]) -> Self {
+        self.wpa_versions = Some(versions.iter().map(|v| u32::from(u16::from(v))).sum());
+        self
+    }
+
+    /// Pairwise cipher suites, setting them marks the connection as protected
+    pub fn pairwise_ciphers(mut self, ciphers: &[u32]) -> Self {
+        self.pairwise_ciphers = Some(ciphers.to_vec());
+        self
+    }
+
+    /// Group cipher suite, setting it marks the connection as protected
+    pub fn group_cipher(mut self, cipher: u32) -> Self {
+        self.group_cipher = Some(cipher);
+        self
+    }
+
+    /// AKM suites
+    pub fn akm_suites(mut self, suites: &[u32]) -> Self {
+        self.akm_suites = Some(suites.to_vec());
+        self
+    }
+
+    /// PSK (the PMK derived from the passphrase) for drivers that offload the 4-way handshake
+    pub fn psk(mut self, psk: &[u8]) -> Self {
+        self.psk = Some(psk.to_vec());
+        self
+    }
+
+    /// SAE password for drivers that offload SAE authentication
+    pub fn sae_password(mut self, password: &[u8]) -> Self {
+        self.sae_password = Some(password.to_vec());
+        self
+    }
+
+    /// Management frame protection
+    pub fn mfp(mut self, mfp: Nl80211Mfp) -> Self {
+        self.mfp = Some(mfp);
+        self
+    }
+
+    /// Extra information elements to add to the (re)association request
+    pub fn ie(mut self, ie: &[u8]) -> Self {
+        self.ie = Some(ie.to_vec());
+        self
+    }
+
+    /// BSS selection behaviour
+    pub fn bss_select(mut self, bss_select: BssSelect) -> Self {
+        self.bss_select = Some(bss_select);
+        self
+    }
+
+    pub(crate) fn append_attrs(
+        &self,
+        attrs: &mut GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), SerError> {
+        attrs.push(new_attr(Nl80211Attr::AttrSsid, self.ssid.as_slice())?);
+        if let Some(bssid) = &self.bssid {
+            attrs.push(new_attr(Nl80211Attr::AttrMac, bssid.as_slice())?);
+        }
+        if let Some(bssid) = &self.bssid_hint {
+            attrs.push(new_attr(Nl80211Attr::AttrMacHint, bssid.as_slice())?);
+        }
+        if let Some(frequency) = self.frequency {
+            attrs.push(new_attr(Nl80211Attr::AttrWiphyFreq, frequency)?);
+        }
+        if let Some(frequency) = self.frequency_hint {
+            attrs.push(new_attr(Nl80211Attr::AttrWiphyFreqHint, frequency)?);
+        }
+        if let Some(auth_type) = self.auth_type {
+            attrs.push(new_attr(
+                Nl80211Attr::AttrAuthType,
+                u32::from(u16::from(auth_type)),
+            )?);
+        }
+        if self.pairwise_ciphers.is_some() || self.group_cipher.is_some() {
+            attrs.push(new_attr(Nl80211Attr::AttrPrivacy, ())?);
+        }
+        if let Some(versions) = self.wpa_versions {
+            attrs.push(new_attr(Nl80211Attr::AttrWpaVersions, versions)?);
+        }
+        if let Some(ciphers) = &self.pairwise_ciphers {
+            attrs.push(new_attr(
+                Nl80211Attr::AttrCipherSuitesPairwise,
+                ciphers.clone(),
+            )?);
+        }
+        if let Some(cipher) = self.group_cipher {
+            attrs.push(new_attr(Nl80211Attr::AttrCipherSuiteGroup, cipher)?);
+        }
+        if let Some(suites) = &self.akm_suites {
+            attrs.push(new_attr(Nl80211Attr::AttrAkmSuites, suites.clone())?);
+        }
+        if let Some(psk) = &self.psk {
+            attrs.push(new_attr(Nl80211Attr::AttrPmk, psk.as_slice())?);
+        }
+        if let Some(password) = &self.sae_password {
+            attrs.push(new_attr(Nl80211Attr::AttrSaePassword, password.as_slice())?);
+        }
+        if let Some(mfp) = self.mfp {
+            attrs.push(new_attr(
+                Nl80211Attr::AttrUseMfp,
+                u32::from(u16::from(mfp)),
+            )?);
+        }
+        if let Some(ie) = &self.ie {
+            attrs.push(new_attr(Nl80211Attr::AttrIe, ie.as_slice())?);
+        }
+        if let Some(bss_select) = self.bss_select {
+            let select = match bss_select {
+                BssSelect::Rssi => new_attr(Nl80211BssSelectAttr::BssSelectAttrRssi, ())?,
+                BssSelect::BandPref(band) => new_attr(
+                    Nl80211BssSelectAttr::BssSelectAttrBandPref,
+                    u32::from(u16::from(band)),
+                )?,
+                BssSelect::RssiAdjust { band, delta } => new_attr(
+                    Nl80211BssSelectAttr::BssSelectAttrRssiAdjust,
+                    &[u16::from(band) as u8, delta as u8][..],
+                )?,
+            };
+            attrs.push(new_nested_attr(Nl80211Attr::AttrBssSelect, &[select])?);
+        }
+        Ok(())
+    }
+}
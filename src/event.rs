@@ -0,0 +1,493 @@
+use crate::attr::{
+    Nl80211Attr, Nl80211ConnectFailedReason, Nl80211DfsState, Nl80211KeyType,
+    Nl80211PmksaCandidateAttr, Nl80211RadarEvent, Nl80211RekeyData,
+};
+use crate::channel::ChannelDef;
+use crate::cmd::Nl80211Cmd;
+
+use neli::attr::Attribute;
+use neli::err::DeError;
+use neli::genl::Genlmsghdr;
+
+/// A notification sent by the kernel on the "mlme" multicast group
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// Result of a connection attempt
+    Connect {
+        /// Interface index
+        interface_index: i32,
+        /// BSSID of the network
+        bssid: Option<Vec<u8>>,
+        /// Status code of the association response, 0 on success
+        status_code: Option<u16>,
+        /// The connection attempt timed out without a response from the AP
+        timed_out: bool,
+        /// Information elements of the association request
+        req_ie: Option<Vec<u8>>,
+        /// Information elements of the association response
+        resp_ie: Option<Vec<u8>>,
+    },
+    /// The device roamed to another BSS of the same network
+    Roam {
+        /// Interface index
+        interface_index: i32,
+        /// BSSID of the new BSS
+        bssid: Option<Vec<u8>>,
+        /// Information elements of the reassociation request
+        req_ie: Option<Vec<u8>>,
+        /// Information elements of the reassociation response
+        resp_ie: Option<Vec<u8>>,
+    },
+    /// The device was disconnected from the network
+    Disconnect {
+        /// Interface index
+        interface_index: i32,
+        /// Reason code of the disconnection
+        reason_code: Option<u16>,
+        /// The disconnection was initiated by the AP
+        by_ap: bool,
+    },
+    /// The connection attempt was rejected by the AP
+    ConnFailed {
+        /// Interface index
+        interface_index: i32,
+        /// MAC address of the AP
+        bssid: Option<Vec<u8>>,
+        /// Reason of the failure
+        reason: Option<Nl80211ConnectFailedReason>,
+    },
+    /// A Michael MIC (TKIP) failure was detected
+    MichaelMicFailure {
+        /// Interface index
+        interface_index: i32,
+        /// Source MAC address of the frame
+        address: Option<Vec<u8>>,
+        /// Type of the key used for the frame
+        key_type: Option<Nl80211KeyType>,
+        /// Index of the key used for the frame
+        key_index: Option<u8>,
+        /// TSC of the frame
+        key_seq: Option<Vec<u8>>,
+    },
+    /// An unprotected deauthentication frame was dropped because MFP is in use
+    UnprotDeauthenticate {
+        /// Interface index
+        interface_index: i32,
+        /// The dropped frame
+        frame: Vec<u8>,
+    },
+    /// The operating channel of the interface changed
+    ChannelSwitch {
+        /// Interface index
+        interface_index: i32,
+        /// The new channel
+        channel: ChannelDef,
+    },
+    /// A channel switch was started, the switch happens after `count` beacon intervals
+    ChannelSwitchStarted {
+        /// Interface index
+        interface_index: i32,
+        /// The new channel
+        channel: ChannelDef,
+        /// Number of beacon intervals before the switch
+        count: Option<u32>,
+    },
+    /// A radar was detected or the state of a CAC changed
+    Radar {
+        /// Wiphy index
+        wiphy: u32,
+        /// Interface index, not set for events not tied to an interface like the end of a NOP
+        interface_index: Option<i32>,
+        /// What happened
+        event: Nl80211RadarEvent,
+        /// The channel concerned
+        channel: ChannelDef,
+        /// DFS state of the channel after the event, derived from the event as the kernel doesn't
+        /// report it, not set for unknown events
+        dfs_state: Option<Nl80211DfsState>,
+    },
+    /// The driver found a BSS for which a PMKSA could be established by pre-authentication
+    PmksaCandidate {
+        /// Interface index
+        interface_index: i32,
+        /// Priority of the candidate, lower is better
+        index: Option<u32>,
+        /// BSSID of the candidate
+        bssid: Option<Vec<u8>>,
+        /// The candidate supports RSN pre-authentication
+        preauth: bool,
+    },
+    /// The device completed a group rekey on its own
+    GtkRekey {
+        /// Interface index
+        interface_index: i32,
+        /// BSSID of the AP
+        bssid: Option<Vec<u8>>,
+        /// The new EAPOL-Key replay counter
+        replay_counter: Option<Vec<u8>>,
+    },
+}
+
+impl Event {
+    /// Decode a notification, unknown and malformed notifications are ignored
+    pub(crate) fn from_msg(msg: &Genlmsghdr<Nl80211Cmd, Nl80211Attr>) -> Option<Self> {
+        Self::decode(msg).ok().flatten()
+    }
+
+    fn decode(msg: &Genlmsghdr<Nl80211Cmd, Nl80211Attr>) -> Result<Option<Self>, DeError> {
+        let mut interface_index = None;
+        let mut bssid = None;
+        let mut status_code = None;
+        let mut timed_out = false;
+        let mut req_ie = None;
+        let mut resp_ie = None;
+        let mut reason_code = None;
+        let mut by_ap = false;
+        let mut reason = None;
+        let mut key_type = None;
+        let mut key_index = None;
+        let mut key_seq = None;
+        let mut frame = None;
+        let mut candidate_index = None;
+        let mut candidate_bssid = None;
+        let mut preauth = false;
+        let mut replay_counter = None;
+        let mut count = None;
+        let mut wiphy = None;
+        let mut radar_event = None;
+        for attr in msg.get_attr_handle().iter() {
+            match attr.nla_type.nla_type {
+                Nl80211Attr::AttrWiphy => wiphy = Some(attr.get_payload_as()?),
+                Nl80211Attr::AttrIfindex => interface_index = Some(attr.get_payload_as()?),
+                Nl80211Attr::AttrMac => bssid = Some(attr.get_payload_as_with_len()?),
+                Nl80211Attr::AttrStatusCode => status_code = Some(attr.get_payload_as()?),
+                Nl80211Attr::AttrTimedOut => timed_out = true,
+                Nl80211Attr::AttrReqIe => req_ie = Some(attr.get_payload_as_with_len()?),
+                Nl80211Attr::AttrRespIe => resp_ie = Some(attr.get_payload_as_with_len()?),
+                Nl80211Attr::AttrReasonCode => reason_code = Some(attr.get_payload_as()?),
+                Nl80211Attr::AttrDisconnectedByAp => by_ap = true,
+                Nl80211Attr::AttrConnFailedReason => {
+                    let value: u32 = attr.get_payload_as()?;
+                    reason = Some(Nl80211ConnectFailedReason::from(value as u16));
+                }
+                Nl80211Attr::AttrKeyType => {
+                    let value: u32 = attr.get_payload_as()?;
+                    key_type = Some(Nl80211KeyType::from(value as u16));
+                }
+                Nl80211Attr::AttrKeyIdx => key_index = Some(attr.get_payload_as()?),
+                Nl80211Attr::AttrKeySeq => key_seq = Some(attr.get_payload_as_with_len()?),
+                Nl80211Attr::AttrChSwitchCount => count = Some(attr.get_payload_as()?),
+                Nl80211Attr::AttrFrame => frame = Some(attr.get_payload_as_with_len()?),
+                Nl80211Attr::AttrRadarEvent => {
+                    let value: u32 = attr.get_payload_as()?;
+                    radar_event = Some(Nl80211RadarEvent::from(value as u16));
+                }
+                Nl80211Attr::AttrPmksaCandidate => {
+                    let attrs = attr.get_attr_handle::<Nl80211PmksaCandidateAttr>()?;
+                    for attr in attrs.iter() {
+                        match attr.nla_type.nla_type {
+                            Nl80211PmksaCandidateAttr::PmksaCandidateIndex => {
+                                candidate_index = Some(attr.get_payload_as()?)
+                            }
+                            Nl80211PmksaCandidateAttr::PmksaCandidateBssid => {
+                                candidate_bssid = Some(attr.get_payload_as_with_len()?)
+                            }
+                            Nl80211PmksaCandidateAttr::PmksaCandidatePreauth => preauth = true,
+                            _ => (),
+                        }
+                    }
+                }
+                Nl80211Attr::AttrRekeyData => {
+                    let attrs = attr.get_attr_handle::<Nl80211RekeyData>()?;
+                    for attr in attrs.iter() {
+                        if attr.nla_type.nla_type == Nl80211RekeyData::RekeyDataReplayCtr {
+                            replay_counter = Some(attr.get_payload_as_with_len()?);
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        if let (Nl80211Cmd::CmdRadarDetect, Some(wiphy), Some(event)) =
+            (msg.cmd, wiphy, radar_event)
+        {
+            let dfs_state = match event {
+                Nl80211RadarEvent::RadarDetected => Some(Nl80211DfsState::DfsUnavailable),
+                Nl80211RadarEvent::RadarCacFinished => Some(Nl80211DfsState::DfsAvailable),
+                Nl80211RadarEvent::RadarCacAborted
+                | Nl80211RadarEvent::RadarNopFinished
+                | Nl80211RadarEvent::RadarPreCacExpired
+                | Nl80211RadarEvent::RadarCacStarted => Some(Nl80211DfsState::DfsUsable),
+                _ => None,
+            };
+            return Ok(Some(Event::Radar {
+                wiphy,
+                interface_index,
+                event,
+                channel: msg.get_attr_handle().try_into()?,
+                dfs_state,
+            }));
+        }
+
+        let interface_index = match interface_index {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+        let event = match msg.cmd {
+            Nl80211Cmd::CmdConnect => Event::Connect {
+                interface_index,
+                bssid,
+                status_code,
+                timed_out,
+                req_ie,
+                resp_ie,
+            },
+            Nl80211Cmd::CmdRoam => Event::Roam {
+                interface_index,
+                bssid,
+                req_ie,
+                resp_ie,
+            },
+            Nl80211Cmd::CmdDisconnect => Event::Disconnect {
+                interface_index,
+                reason_code,
+                by_ap,
+            },
+            Nl80211Cmd::CmdConnFailed => Event::ConnFailed {
+                interface_index,
+                bssid,
+                reason,
+            },
+            Nl80211Cmd::CmdMichaelMicFailure => Event::MichaelMicFailure {
+                interface_index,
+                address: bssid,
+                key_type,
+                key_index,
+                key_seq,
+            },
+            Nl80211Cmd::CmdUnprotDeauthenticate => Event::UnprotDeauthenticate {
+                interface_index,
+                frame: frame.ok_or_else(|| DeError::new("Missing frame"))?,
+            },
+            Nl80211Cmd::CmdChSwitchNotify => Event::ChannelSwitch {
+                interface_index,
+                channel: msg.get_attr_handle().try_into()?,
+            },
+            Nl80211Cmd::CmdChSwitchStartedNotify => Event::ChannelSwitchStarted {
+                interface_index,
+                channel: msg.get_attr_handle().try_into()?,
+                count,
+            },
+            Nl80211Cmd::CmdPmksaCandidate => Event::PmksaCandidate {
+                interface_index,
+                index: candidate_index,
+                bssid: candidate_bssid,
+                preauth,
+            },
+            Nl80211Cmd::CmdSetRekeyOffload => Event::GtkRekey {
+                interface_index,
+                bssid,
+                replay_counter,
+            },
+            _ => return Ok(None),
+        };
+        Ok(Some(event))
+    }
+}
+
+#[cfg(test)]
+mod test_event {
+    use super::*;
+    use crate::attr::Nl80211Attr::*;
+    use crate::attr::Nl80211ChanWidth;
+    use crate::NL_80211_GENL_VERSION;
+    use neli::genl::{AttrType, Nlattr};
+    use neli::types::Buffer;
+
+    fn new_attr(t: Nl80211Attr, d: Vec<u8>) -> Nlattr<Nl80211Attr, Buffer> {
+        Nlattr {
+            nla_len: (4 + d.len()) as _,
+            nla_type: AttrType {
+                nla_nested: false,
+                nla_network_order: true,
+                nla_type: t,
+            },
+            nla_payload: d.into(),
+        }
+    }
+
+    fn new_msg(cmd: Nl80211Cmd, attrs: Vec<Nlattr<Nl80211Attr, Buffer>>) -> Option<Event> {
+        let msg = Genlmsghdr::new(cmd, NL_80211_GENL_VERSION, attrs.into_iter().collect());
+        Event::from_msg(&msg)
+    }
+
+    #[test]
+    fn test_disconnect() {
+        let event = new_msg(
+            Nl80211Cmd::CmdDisconnect,
+            vec![
+                new_attr(AttrWiphy, vec![0, 0, 0, 0]),
+                new_attr(AttrIfindex, vec![3, 0, 0, 0]),
+                new_attr(AttrReasonCode, vec![3, 0]),
+                new_attr(AttrDisconnectedByAp, vec![]),
+            ],
+        );
+
+        let expected_event = Event::Disconnect {
+            interface_index: 3,
+            reason_code: Some(3),
+            by_ap: true,
+        };
+
+        assert_eq!(event, Some(expected_event))
+    }
+
+    #[test]
+    fn test_channel_switch() {
+        let event = new_msg(
+            Nl80211Cmd::CmdChSwitchNotify,
+            vec![
+                new_attr(AttrIfindex, vec![3, 0, 0, 0]),
+                new_attr(AttrWiphyFreq, vec![124, 21, 0, 0]),
+                new_attr(AttrChannelWidth, vec![3, 0, 0, 0]),
+                new_attr(AttrCenterFreq1, vec![154, 21, 0, 0]),
+            ],
+        );
+
+        let expected_event = Event::ChannelSwitch {
+            interface_index: 3,
+            channel: ChannelDef::new(5500).with_width(Nl80211ChanWidth::ChanWidth80, 5530),
+        };
+
+        assert_eq!(event, Some(expected_event))
+    }
+
+    #[test]
+    fn test_channel_switch_started() {
+        let event = new_msg(
+            Nl80211Cmd::CmdChSwitchStartedNotify,
+            vec![
+                new_attr(AttrIfindex, vec![3, 0, 0, 0]),
+                new_attr(AttrWiphyFreq, vec![108, 9, 0, 0]),
+                new_attr(AttrChannelWidth, vec![0, 0, 0, 0]),
+                new_attr(AttrChSwitchCount, vec![5, 0, 0, 0]),
+            ],
+        );
+
+        let expected_event = Event::ChannelSwitchStarted {
+            interface_index: 3,
+            channel: ChannelDef::new(2412),
+            count: Some(5),
+        };
+
+        assert_eq!(event, Some(expected_event))
+    }
+
+    #[test]
+    fn test_radar() {
+        let event = new_msg(
+            Nl80211Cmd::CmdRadarDetect,
+            vec![
+                new_attr(AttrWiphy, vec![0, 0, 0, 0]),
+                new_attr(AttrRadarEvent, vec![3, 0, 0, 0]),
+                new_attr(AttrWiphyFreq, vec![124, 21, 0, 0]),
+                new_attr(AttrChannelWidth, vec![1, 0, 0, 0]),
+                new_attr(AttrCenterFreq1, vec![124, 21, 0, 0]),
+            ],
+        );
+
+        let expected_event = Event::Radar {
+            wiphy: 0,
+            interface_index: None,
+            event: Nl80211RadarEvent::RadarNopFinished,
+            channel: ChannelDef::new(5500).with_width(Nl80211ChanWidth::ChanWidth20, 5500),
+            dfs_state: Some(Nl80211DfsState::DfsUsable),
+        };
+
+        assert_eq!(event, Some(expected_event))
+    }
+
+    #[test]
+    fn test_malformed_radar() {
+        let event = new_msg(
+            Nl80211Cmd::CmdRadarDetect,
+            vec![
+                new_attr(AttrWiphy, vec![0, 0, 0, 0]),
+                new_attr(AttrRadarEvent, vec![0, 0, 0, 0]),
+                new_attr(AttrChannelWidth, vec![1, 0, 0, 0]),
+            ],
+        );
+
+        assert_eq!(event, None)
+    }
+
+    #[test]
+    fn test_malformed_channel_switch() {
+        let event = new_msg(
+            Nl80211Cmd::CmdChSwitchNotify,
+            vec![
+                new_attr(AttrIfindex, vec![3, 0, 0, 0]),
+                new_attr(AttrWiphyFreq, vec![124, 21]),
+            ],
+        );
+
+        assert_eq!(event, None)
+    }
+
+    #[test]
+    fn test_pmksa_candidate() {
+        let event = new_msg(
+            Nl80211Cmd::CmdPmksaCandidate,
+            vec![
+                new_attr(AttrIfindex, vec![3, 0, 0, 0]),
+                new_attr(
+                    AttrPmksaCandidate,
+                    vec![
+                        8, 0, 1, 0, 1, 0, 0, 0, 10, 0, 2, 0, 2, 0, 0, 0, 1, 0, 0, 0, 4, 0, 3, 0,
+                    ],
+                ),
+            ],
+        );
+
+        let expected_event = Event::PmksaCandidate {
+            interface_index: 3,
+            index: Some(1),
+            bssid: Some(vec![2, 0, 0, 0, 1, 0]),
+            preauth: true,
+        };
+
+        assert_eq!(event, Some(expected_event))
+    }
+
+    #[test]
+    fn test_gtk_rekey() {
+        let event = new_msg(
+            Nl80211Cmd::CmdSetRekeyOffload,
+            vec![
+                new_attr(AttrIfindex, vec![3, 0, 0, 0]),
+                new_attr(AttrMac, vec![2, 0, 0, 0, 1, 0]),
+                new_attr(AttrRekeyData, vec![12, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 5]),
+            ],
+        );
+
+        let expected_event = Event::GtkRekey {
+            interface_index: 3,
+            bssid: Some(vec![2, 0, 0, 0, 1, 0]),
+            replay_counter: Some(vec![0, 0, 0, 0, 0, 0, 0, 5]),
+        };
+
+        assert_eq!(event, Some(expected_event))
+    }
+
+    #[test]
+    fn test_unknown() {
+        let event = new_msg(
+            Nl80211Cmd::CmdNewStation,
+            vec![new_attr(AttrIfindex, vec![3, 0, 0, 0])],
+        );
+
+        assert_eq!(event, None)
+    }
+}
@@ -1,7 +1,16 @@
-use crate::attr::{Attrs, Nl80211Attr};
+use crate::attr::{
+    new_attr, new_nested_attr, Attrs, Nl80211Attr, Nl80211ChanWidth, Nl80211ChannelType,
+    Nl80211Iftype, Nl80211MntrFlags, Nl80211TxqStats,
+};
+use crate::channel::{frequency_to_channel, ChannelDef};
+
+use std::error::Error;
+use std::ffi::CString;
+use std::{fmt, io};
 
 use neli::attr::Attribute;
-use neli::err::DeError;
+use neli::err::{DeError, NlError, SerError};
+use neli::types::{Buffer, GenlBuffer};
 
 /// A struct representing a wifi interface
 #[non_exhaustive]
@@ -15,16 +24,30 @@ pub struct Interface {
     pub mac: Option<Vec<u8>>,
     /// Interface name (u8, String)
     pub name: Option<Vec<u8>>,
+    /// Interface type
+    pub iftype: Option<Nl80211Iftype>,
+    /// Interface uses 4-address frames
+    pub four_addr: Option<bool>,
     /// Interface frequency of the selected channel (MHz)
     pub frequency: Option<u32>,
-    /// Interface chanel
+    /// Offset of the frequency of the selected channel (KHz)
+    pub frequency_offset: Option<u32>,
+    /// Interface channel number
     pub channel: Option<u32>,
+    /// Interface channel definition
+    pub channel_def: Option<ChannelDef>,
+    /// Interface channel type, only set for 20 and 40 MHz channels
+    pub channel_type: Option<Nl80211ChannelType>,
     /// Interface transmit power level in signed mBm units.
-    pub power: Option<u32>,
+    pub power: Option<i32>,
     /// index of wiphy to operate on, cf. /sys/class/ieee80211/<phyname>/index
     pub phy: Option<u32>,
     /// Wireless device identifier, used for pseudo-devices that don't have a netdev
     pub device: Option<u64>,
+    /// Generation of the interface list, changes when interfaces are added or removed
+    pub generation: Option<u32>,
+    /// Statistics of the TX queues of the interface
+    pub txq_stats: Option<TxqStats>,
 }
 
 impl TryFrom<Attrs<'_, Nl80211Attr>> for Interface {
@@ -32,6 +55,9 @@ impl TryFrom<Attrs<'_, Nl80211Attr>> for Interface {
 
     fn try_from(attrs: Attrs<'_, Nl80211Attr>) -> Result<Self, Self::Error> {
         let mut res = Self::default();
+        let mut width = None;
+        let mut center_freq1 = None;
+        let mut center_freq2 = None;
         for attr in attrs.iter() {
             match attr.nla_type.nla_type {
                 Nl80211Attr::AttrIfindex => {
@@ -46,17 +72,115 @@ impl TryFrom<Attrs<'_, Nl80211Attr>> for Interface {
                 Nl80211Attr::AttrIfname => {
                     res.name = Some(attr.get_payload_as_with_len()?);
                 }
+                Nl80211Attr::AttrIftype => {
+                    let iftype: u32 = attr.get_payload_as()?;
+                    res.iftype = Some(Nl80211Iftype::from(iftype as u16));
+                }
+                Nl80211Attr::Attr4addr => {
+                    res.four_addr = Some(attr.get_payload_as::<u8>()? != 0);
+                }
                 Nl80211Attr::AttrWiphyFreq => {
                     res.frequency = Some(attr.get_payload_as()?);
                 }
+                Nl80211Attr::AttrWiphyFreqOffset => {
+                    res.frequency_offset = Some(attr.get_payload_as()?);
+                }
                 Nl80211Attr::AttrChannelWidth => {
-                    res.channel = Some(attr.get_payload_as()?);
+                    let value: u32 = attr.get_payload_as()?;
+                    width = Some(Nl80211ChanWidth::from(value as u16));
+                }
+                Nl80211Attr::AttrCenterFreq1 => center_freq1 = Some(attr.get_payload_as()?),
+                Nl80211Attr::AttrCenterFreq2 => center_freq2 = Some(attr.get_payload_as()?),
+                Nl80211Attr::AttrWiphyChannelType => {
+                    let value: u32 = attr.get_payload_as()?;
+                    res.channel_type = Some(Nl80211ChannelType::from(value as u16));
                 }
                 Nl80211Attr::AttrWiphyTxPowerLevel => {
                     res.power = Some(attr.get_payload_as()?);
                 }
                 Nl80211Attr::AttrWiphy => res.phy = Some(attr.get_payload_as()?),
                 Nl80211Attr::AttrWdev => res.device = Some(attr.get_payload_as()?),
+                Nl80211Attr::AttrGeneration => res.generation = Some(attr.get_payload_as()?),
+                Nl80211Attr::AttrTxqStats => {
+                    res.txq_stats = Some(attr.get_attr_handle::<Nl80211TxqStats>()?.try_into()?);
+                }
+                _ => (),
+            }
+        }
+        if let Some(frequency) = res.frequency {
+            res.channel = frequency_to_channel(frequency);
+            res.channel_def = Some(ChannelDef {
+                frequency,
+                width: width.unwrap_or(Nl80211ChanWidth::ChanWidth20Noht),
+                center_freq1,
+                center_freq2,
+            });
+        }
+        Ok(res)
+    }
+}
+
+impl Interface {
+    /// Transmit power level in dBm
+    pub fn power_dbm(&self) -> Option<f64> {
+        self.power.map(|power| f64::from(power) / 100.0)
+    }
+}
+
+/// A struct representing the statistics of TX queues
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TxqStats {
+    /// Number of bytes currently queued
+    pub backlog_bytes: Option<u32>,
+    /// Number of packets currently queued
+    pub backlog_packets: Option<u32>,
+    /// Number of flows with queued packets
+    pub flows: Option<u32>,
+    /// Number of dropped packets
+    pub drops: Option<u32>,
+    /// Number of packets marked with ECN
+    pub ecn_marks: Option<u32>,
+    /// Number of packets dropped because the queue limit was reached
+    pub overlimit: Option<u32>,
+    /// Number of packets dropped because the memory limit was reached
+    pub overmemory: Option<u32>,
+    /// Number of hash collisions between flows
+    pub collisions: Option<u32>,
+    /// Number of transmitted bytes
+    pub tx_bytes: Option<u32>,
+    /// Number of transmitted packets
+    pub tx_packets: Option<u32>,
+    /// Number of flows that can be tracked
+    pub max_flows: Option<u32>,
+}
+
+impl TryFrom<Attrs<'_, Nl80211TxqStats>> for TxqStats {
+    type Error = DeError;
+
+    fn try_from(attrs: Attrs<'_, Nl80211TxqStats>) -> Result<Self, Self::Error> {
+        let mut res = Self::default();
+        for attr in attrs.iter() {
+            match attr.nla_type.nla_type {
+                Nl80211TxqStats::TxqStatsBacklogBytes => {
+                    res.backlog_bytes = Some(attr.get_payload_as()?)
+                }
+                Nl80211TxqStats::TxqStatsBacklogPackets => {
+                    res.backlog_packets = Some(attr.get_payload_as()?)
+                }
+                Nl80211TxqStats::TxqStatsFlows => res.flows = Some(attr.get_payload_as()?),
+                Nl80211TxqStats::TxqStatsDrops => res.drops = Some(attr.get_payload_as()?),
+                Nl80211TxqStats::TxqStatsEcnMarks => res.ecn_marks = Some(attr.get_payload_as()?),
+                Nl80211TxqStats::TxqStatsOverlimit => res.overlimit = Some(attr.get_payload_as()?),
+                Nl80211TxqStats::TxqStatsOvermemory => {
+                    res.overmemory = Some(attr.get_payload_as()?)
+                }
+                Nl80211TxqStats::TxqStatsCollisions => {
+                    res.collisions = Some(attr.get_payload_as()?)
+                }
+                Nl80211TxqStats::TxqStatsTxBytes => res.tx_bytes = Some(attr.get_payload_as()?),
+                Nl80211TxqStats::TxqStatsTxPackets => res.tx_packets = Some(attr.get_payload_as()?),
+                Nl80211TxqStats::TxqStatsMaxFlows => res.max_flows = Some(attr.get_payload_as()?),
                 _ => (),
             }
         }
@@ -64,6 +188,144 @@ impl TryFrom<Attrs<'_, Nl80211Attr>> for Interface {
     }
 }
 
+/// Identifies a wireless device, by the index of its netdev or by its wdev identifier
+///
+/// Only the wdev identifier is available for devices without a netdev, like P2P devices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterfaceId {
+    /// Netlink interface index
+    Index(i32),
+    /// Wireless device identifier
+    Wdev(u64),
+}
+
+impl InterfaceId {
+    pub(crate) fn append_attrs(
+        &self,
+        attrs: &mut GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), SerError> {
+        match *self {
+            InterfaceId::Index(index) => attrs.push(new_attr(Nl80211Attr::AttrIfindex, index)?),
+            InterfaceId::Wdev(wdev) => attrs.push(new_attr(Nl80211Attr::AttrWdev, wdev)?),
+        }
+        Ok(())
+    }
+}
+
+/// Get the index of the network interface `name`
+pub(crate) fn index_from_name(name: &str) -> Result<i32, NlError> {
+    let name = CString::new(name).map_err(|_| NlError::msg("Invalid interface name"))?;
+    // SAFETY: `name` is a valid nul-terminated string
+    match unsafe { libc::if_nametoindex(name.as_ptr()) } {
+        0 => Err(io::Error::last_os_error().into()),
+        index => Ok(index as i32),
+    }
+}
+
+/// Options of a new interface
+///
+/// # Example
+///
+/// ```
+/// # use neli_wifi::{InterfaceOptions, Nl80211MntrFlags};
+/// let options = InterfaceOptions::new()
+///     .monitor_flags(&[Nl80211MntrFlags::MntrFlagControl, Nl80211MntrFlags::MntrFlagOtherBss]);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct InterfaceOptions {
+    monitor_flags: Option<Vec<Nl80211MntrFlags>>,
+    four_addr: Option<bool>,
+    mac: Option<Vec<u8>>,
+}
+
+impl InterfaceOptions {
+    /// Default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Monitor flags, only used by monitor interfaces
+    pub fn monitor_flags(mut self, flags: &[Nl80211MntrFlags]) -> Self {
+        self.monitor_flags = Some(flags.to_vec());
+        self
+    }
+
+    /// Use 4-address frames
+    pub fn four_addr(mut self, four_addr: bool) -> Self {
+        self.four_addr = Some(four_addr);
+        self
+    }
+
+    /// MAC address of the interface
+    pub fn mac(mut self, mac: &[u8]) -> Self {
+        self.mac = Some(mac.to_vec());
+        self
+    }
+
+    pub(crate) fn append_attrs(
+        &self,
+        attrs: &mut GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), SerError> {
+        if let Some(flags) = &self.monitor_flags {
+            let flags = flags
+                .iter()
+                .map(|flag| new_attr(*flag, ()))
+                .collect::<Result<Vec<_>, _>>()?;
+            attrs.push(new_nested_attr(Nl80211Attr::AttrMntrFlags, &flags)?);
+        }
+        if let Some(four_addr) = self.four_addr {
+            attrs.push(new_attr(Nl80211Attr::Attr4addr, four_addr as u8)?);
+        }
+        if let Some(mac) = &self.mac {
+            attrs.push(new_attr(Nl80211Attr::AttrMac, mac.as_slice())?);
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when changing the type or the mode of an interface
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum SetInterfaceError {
+    /// The interface is busy, bringing it down and retrying may help
+    Busy,
+    /// Any other error
+    Other(NlError),
+}
+
+impl fmt::Display for SetInterfaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SetInterfaceError::Busy => write!(f, "Interface is busy"),
+            SetInterfaceError::Other(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for SetInterfaceError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SetInterfaceError::Busy => None,
+            SetInterfaceError::Other(err) => Some(err),
+        }
+    }
+}
+
+impl From<NlError> for SetInterfaceError {
+    fn from(err: NlError) -> Self {
+        match err {
+            NlError::Nlmsgerr(ref msg) if msg.error == -libc::EBUSY => SetInterfaceError::Busy,
+            err => SetInterfaceError::Other(err),
+        }
+    }
+}
+
+impl From<SerError> for SetInterfaceError {
+    fn from(err: SerError) -> Self {
+        SetInterfaceError::Other(NlError::Ser(err))
+    }
+}
+
 #[cfg(test)]
 mod test_interface {
     use super::*;
@@ -93,9 +355,21 @@ mod test_interface {
             new_attr(AttrIftype, vec![2, 0, 0, 0]),
             new_attr(AttrWdev, vec![1, 0, 0, 0, 0, 0, 0, 0]),
             new_attr(AttrMac, vec![255, 255, 255, 255, 255, 255]),
+            new_attr(AttrGeneration, vec![7, 0, 0, 0]),
+            new_attr(Attr4addr, vec![0]),
             new_attr(AttrWiphyFreq, vec![108, 9, 0, 0]),
+            new_attr(AttrWiphyFreqOffset, vec![0, 0, 0, 0]),
+            new_attr(AttrWiphyChannelType, vec![1, 0, 0, 0]),
             new_attr(AttrChannelWidth, vec![1, 0, 0, 0]),
+            new_attr(AttrCenterFreq1, vec![108, 9, 0, 0]),
             new_attr(AttrWiphyTxPowerLevel, vec![164, 6, 0, 0]),
+            new_attr(
+                AttrTxqStats,
+                vec![
+                    8, 0, 1, 0, 0, 0, 0, 0, 8, 0, 4, 0, 2, 0, 0, 0, 8, 0, 9, 0, 16, 39, 0, 0, 8, 0,
+                    10, 0, 100, 0, 0, 0,
+                ],
+            ),
             new_attr(AttrSsid, vec![101, 100, 117, 114, 111, 97, 109]),
         ];
 
@@ -107,13 +381,81 @@ mod test_interface {
             ssid: Some(vec![101, 100, 117, 114, 111, 97, 109]),
             mac: Some(vec![255, 255, 255, 255, 255, 255]),
             name: Some(vec![119, 108, 112, 53, 115, 48]),
+            iftype: Some(Nl80211Iftype::IftypeStation),
+            four_addr: Some(false),
             frequency: Some(u32::from_le_bytes([108, 9, 0, 0])),
-            channel: Some(u32::from_le_bytes([1, 0, 0, 0])),
-            power: Some(u32::from_le_bytes([164, 6, 0, 0])),
+            frequency_offset: Some(0),
+            channel: Some(1),
+            channel_def: Some(
+                ChannelDef::new(2412).with_width(Nl80211ChanWidth::ChanWidth20, 2412),
+            ),
+            channel_type: Some(Nl80211ChannelType::ChanHt20),
+            power: Some(i32::from_le_bytes([164, 6, 0, 0])),
             phy: Some(u32::from_le_bytes([0, 0, 0, 0])),
             device: Some(u64::from_le_bytes([1, 0, 0, 0, 0, 0, 0, 0])),
+            generation: Some(7),
+            txq_stats: Some(TxqStats {
+                backlog_bytes: Some(0),
+                drops: Some(2),
+                tx_bytes: Some(10000),
+                tx_packets: Some(100),
+                ..Default::default()
+            }),
         };
 
         assert_eq!(interface, expected_interface)
     }
+
+    #[test]
+    fn test_txq_stats_unknown_attr() {
+        let handler = vec![
+            new_attr(AttrIfindex, vec![3, 0, 0, 0]),
+            new_attr(
+                AttrTxqStats,
+                vec![
+                    8, 0, 4, 0, 2, 0, 0, 0, 6, 0, 13, 0, 1, 0, 0, 0, 8, 0, 10, 0, 100, 0, 0, 0,
+                ],
+            ),
+        ];
+
+        let interface: Interface = AttrHandle::new(handler.into_iter().collect())
+            .try_into()
+            .unwrap();
+        let expected_stats = TxqStats {
+            drops: Some(2),
+            tx_packets: Some(100),
+            ..Default::default()
+        };
+
+        assert_eq!(interface.txq_stats, Some(expected_stats))
+    }
+
+    #[test]
+    fn test_busy_error() {
+        use neli::consts::nl::NlmFFlags;
+        use neli::err::{Nlmsgerr, NlmsghdrErr};
+
+        let nlmsgerr = |error| {
+            NlError::Nlmsgerr(Nlmsgerr {
+                error,
+                nlmsg: NlmsghdrErr {
+                    nl_len: 16,
+                    nl_type: 28,
+                    nl_flags: NlmFFlags::empty(),
+                    nl_seq: 0,
+                    nl_pid: 0,
+                    nl_payload: Buffer::new(),
+                },
+            })
+        };
+
+        assert!(matches!(
+            SetInterfaceError::from(nlmsgerr(-16)),
+            SetInterfaceError::Busy
+        ));
+        assert!(matches!(
+            SetInterfaceError::from(nlmsgerr(-22)),
+            SetInterfaceError::Other(_)
+        ));
+    }
 }
@@ -0,0 +1,237 @@
+use crate::attr::{
+    new_attr, new_nested_attr, Attrs, Nl80211Attr, Nl80211KeyAttributes, Nl80211KeyType,
+    Nl80211RekeyData,
+};
+
+use neli::attr::Attribute;
+use neli::err::{DeError, SerError};
+use neli::types::{Buffer, GenlBuffer};
+
+/// A key to install with [`Socket::new_key`](crate::Socket::new_key)
+///
+/// Key indices 0 to 3 are used for pairwise and group keys, 4 and 5 for IGTKs and 6 and 7 for
+/// BIGTKs. The cipher is given as a selector, e.g. `0x000fac04` for CCMP.
+///
+/// # Example
+///
+/// ```
+/// # use neli_wifi::{KeyParams, Nl80211KeyType};
+/// let ptk = KeyParams::new(0, 0x000fac04, &[0; 16])
+///     .mac(&[0x02, 0, 0, 0, 1, 0])
+///     .key_type(Nl80211KeyType::KeytypePairwise);
+/// let gtk = KeyParams::new(1, 0x000fac04, &[0; 16]).seq(&[3, 0, 0, 0, 0, 0]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyParams {
+    index: u8,
+    cipher: u32,
+    data: Vec<u8>,
+    seq: Option<Vec<u8>>,
+    mac: Option<Vec<u8>>,
+    key_type: Option<Nl80211KeyType>,
+}
+
+impl KeyParams {
+    /// Key `data` of the given cipher suite, stored at `index`
+    pub fn new(index: u8, cipher: u32, data: &[u8]) -> Self {
+        Self {
+            index,
+            cipher,
+            data: data.to_vec(),
+            seq: None,
+            mac: None,
+            key_type: None,
+        }
+    }
+
+    /// Receive sequence counter (RSC) to start from, least significant byte first
+    pub fn seq(mut self, seq: &[u8]) -> Self {
+        self.seq = Some(seq.to_vec());
+        self
+    }
+
+    /// MAC address of the peer the key is used with
+    pub fn mac(mut self, mac: &[u8]) -> Self {
+        self.mac = Some(mac.to_vec());
+        self
+    }
+
+    /// Key type, pairwise when a peer address is given and group otherwise if not set
+    pub fn key_type(mut self, key_type: Nl80211KeyType) -> Self {
+        self.key_type = Some(key_type);
+        self
+    }
+
+    pub(crate) fn append_attrs(
+        &self,
+        attrs: &mut GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), SerError> {
+        if let Some(mac) = &self.mac {
+            attrs.push(new_attr(Nl80211Attr::AttrMac, mac.as_slice())?);
+        }
+        let mut key = vec![
+            new_attr(Nl80211KeyAttributes::KeyData, self.data.as_slice())?,
+            new_attr(Nl80211KeyAttributes::KeyIdx, self.index)?,
+            new_attr(Nl80211KeyAttributes::KeyCipher, self.cipher)?,
+        ];
+        if let Some(seq) = &self.seq {
+            key.push(new_attr(Nl80211KeyAttributes::KeySeq, seq.as_slice())?);
+        }
+        if let Some(key_type) = self.key_type {
+            key.push(new_attr(
+                Nl80211KeyAttributes::KeyType,
+                u32::from(u16::from(key_type)),
+            )?);
+        }
+        attrs.push(new_nested_attr(Nl80211Attr::AttrKey, &key)?);
+        Ok(())
+    }
+}
+
+/// Key material used by the device to handle group rekeys on its own
+///
+/// # Example
+///
+/// ```
+/// # use neli_wifi::RekeyParams;
+/// let rekey = RekeyParams::new(&[0; 16], &[0; 16], &[0, 0, 0, 0, 0, 0, 0, 1]).akm(0x000fac02);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RekeyParams {
+    kek: Vec<u8>,
+    kck: Vec<u8>,
+    replay_counter: Vec<u8>,
+    akm: Option<u32>,
+}
+
+impl RekeyParams {
+    /// KEK and KCK of the current PTK and the current EAPOL-Key replay counter
+    pub fn new(kek: &[u8], kck: &[u8], replay_counter: &[u8]) -> Self {
+        Self {
+            kek: kek.to_vec(),
+            kck: kck.to_vec(),
+            replay_counter: replay_counter.to_vec(),
+            akm: None,
+        }
+    }
+
+    /// AKM suite selector of the connection
+    pub fn akm(mut self, akm: u32) -> Self {
+        self.akm = Some(akm);
+        self
+    }
+
+    pub(crate) fn append_attrs(
+        &self,
+        attrs: &mut GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), SerError> {
+        let mut data = vec![
+            new_attr(Nl80211RekeyData::RekeyDataKek, self.kek.as_slice())?,
+            new_attr(Nl80211RekeyData::RekeyDataKck, self.kck.as_slice())?,
+            new_attr(
+                Nl80211RekeyData::RekeyDataReplayCtr,
+                self.replay_counter.as_slice(),
+            )?,
+        ];
+        if let Some(akm) = self.akm {
+            data.push(new_attr(Nl80211RekeyData::RekeyDataAkm, akm)?);
+        }
+        attrs.push(new_nested_attr(Nl80211Attr::AttrRekeyData, &data)?);
+        Ok(())
+    }
+}
+
+/// A struct representing an installed key
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Key {
+    /// Key index
+    pub index: Option<u8>,
+    /// Cipher suite selector
+    pub cipher: Option<u32>,
+    /// Current TX sequence counter, least significant byte first
+    pub seq: Option<Vec<u8>>,
+}
+
+impl TryFrom<Attrs<'_, Nl80211Attr>> for Key {
+    type Error = DeError;
+
+    fn try_from(attrs: Attrs<'_, Nl80211Attr>) -> Result<Self, Self::Error> {
+        let mut res = Self::default();
+        for attr in attrs.iter() {
+            match attr.nla_type.nla_type {
+                Nl80211Attr::AttrKeyIdx => res.index = Some(attr.get_payload_as()?),
+                Nl80211Attr::AttrKeyCipher => res.cipher = Some(attr.get_payload_as()?),
+                Nl80211Attr::AttrKeySeq => res.seq = Some(attr.get_payload_as_with_len()?),
+                Nl80211Attr::AttrKey => {
+                    let attrs = attr.get_attr_handle::<Nl80211KeyAttributes>()?;
+                    for attr in attrs.iter() {
+                        match attr.nla_type.nla_type {
+                            Nl80211KeyAttributes::KeyIdx => {
+                                res.index = Some(attr.get_payload_as()?)
+                            }
+                            Nl80211KeyAttributes::KeyCipher => {
+                                res.cipher = Some(attr.get_payload_as()?)
+                            }
+                            Nl80211KeyAttributes::KeySeq => {
+                                res.seq = Some(attr.get_payload_as_with_len()?)
+                            }
+                            _ => (),
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod test_key {
+    use super::*;
+    use crate::attr::Nl80211Attr::*;
+    use neli::attr::AttrHandle;
+    use neli::genl::{AttrType, Nlattr};
+    use neli::types::Buffer;
+
+    fn new_attr(t: Nl80211Attr, d: Vec<u8>) -> Nlattr<Nl80211Attr, Buffer> {
+        Nlattr {
+            nla_len: (4 + d.len()) as _,
+            nla_type: AttrType {
+                nla_nested: false,
+                nla_network_order: true,
+                nla_type: t,
+            },
+            nla_payload: d.into(),
+        }
+    }
+
+    #[test]
+    fn test_parser() {
+        let handler = vec![
+            new_attr(AttrIfindex, vec![3, 0, 0, 0]),
+            new_attr(AttrKeyIdx, vec![1]),
+            new_attr(AttrKeySeq, vec![42, 1, 0, 0, 0, 0]),
+            new_attr(AttrKeyCipher, vec![4, 172, 15, 0]),
+            new_attr(
+                AttrKey,
+                vec![
+                    10, 0, 4, 0, 42, 1, 0, 0, 0, 0, 0, 0, 8, 0, 3, 0, 4, 172, 15, 0, 5, 0, 2, 0, 1,
+                    0, 0, 0,
+                ],
+            ),
+        ];
+
+        let key: Key = AttrHandle::new(handler.into_iter().collect())
+            .try_into()
+            .unwrap();
+        let expected_key = Key {
+            index: Some(1),
+            cipher: Some(0x000fac04),
+            seq: Some(vec![42, 1, 0, 0, 0, 0]),
+        };
+
+        assert_eq!(key, expected_key)
+    }
+}
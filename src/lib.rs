@@ -9,15 +9,54 @@ pub use cmd::*;
 mod attr;
 pub use attr::*;
 
+mod ap;
+pub use ap::*;
+
+mod beacon;
+pub use beacon::*;
+
+mod bitrate;
+pub use bitrate::*;
+
 mod bss;
 pub use bss::*;
 
+mod channel;
+pub use channel::*;
+
+mod connect;
+pub use connect::*;
+
+mod event;
+pub use event::*;
+
 mod station;
 pub use station::*;
 
 mod interface;
 pub use interface::*;
 
+mod key;
+pub use key::*;
+
+mod mesh;
+pub use mesh::*;
+
+mod mlme;
+pub use mlme::*;
+
+mod mpath;
+pub use mpath::*;
+
+mod pmksa;
+pub use pmksa::*;
+
+mod reg;
+pub use reg::*;
+
+mod wiphy;
+pub use wiphy::*;
+
 mod socket;
 pub use socket::*;
 
@@ -0,0 +1,445 @@
+use crate::attr::{
+    new_attr, new_nested_attr, Attrs, Nl80211Attr, Nl80211MeshPowerMode, Nl80211MeshSetupParams,
+    Nl80211MeshconfParams,
+};
+use crate::channel::ChannelDef;
+
+use neli::attr::Attribute;
+use neli::err::{DeError, SerError};
+use neli::genl::Nlattr;
+use neli::types::{Buffer, GenlBuffer};
+
+/// A struct representing the configuration of a mesh point interface
+///
+/// When used to change the configuration, only the fields that are set are sent to the kernel.
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MeshConfig {
+    /// Initial retry timeout for peer link open frames in TUs
+    pub retry_timeout: Option<u16>,
+    /// Initial retry timeout for peer link confirm frames in TUs
+    pub confirm_timeout: Option<u16>,
+    /// Timeout for peer link close frames in TUs
+    pub holding_timeout: Option<u16>,
+    /// Maximum number of peer links allowed
+    pub max_peer_links: Option<u16>,
+    /// Maximum number of peer link open retries
+    pub max_retries: Option<u8>,
+    /// Time-to-live of mesh path selection frames
+    pub ttl: Option<u8>,
+    /// Whether to automatically open peer links with compatible mesh peers
+    pub auto_open_plinks: Option<bool>,
+    /// Maximum number of HWMP path request retries
+    pub hwmp_max_preq_retries: Option<u8>,
+    /// Interval after which an active path is refreshed in ms
+    pub path_refresh_time: Option<u32>,
+    /// Minimum path discovery timeout in ms
+    pub min_discovery_timeout: Option<u16>,
+    /// Time that paths discovered by HWMP remain valid in TUs
+    pub hwmp_active_path_timeout: Option<u32>,
+    /// Minimum interval between two consecutive path requests in TUs
+    pub hwmp_preq_min_interval: Option<u16>,
+    /// Time for a path frame to traverse the mesh in TUs
+    pub hwmp_net_diameter_traversal_time: Option<u16>,
+    /// HWMP root mode of this mesh point
+    pub hwmp_root_mode: Option<u8>,
+    /// Time-to-live of mesh path selection elements
+    pub element_ttl: Option<u8>,
+    /// Interval of root announcements in TUs
+    pub hwmp_rann_interval: Option<u16>,
+    /// Whether this mesh point advertises that it is a mesh gate
+    pub gate_announcements: Option<bool>,
+    /// Minimum interval between two consecutive path errors in TUs
+    pub hwmp_perr_min_interval: Option<u16>,
+    /// Whether frames are forwarded within the mesh
+    pub forwarding: Option<bool>,
+    /// RSSI threshold for peer links in dBm, 0 means no threshold
+    pub rssi_threshold: Option<i32>,
+    /// Maximum number of neighbors to synchronize to
+    pub sync_offset_max_neighbor: Option<u32>,
+    /// HT operation mode
+    pub ht_opmode: Option<u16>,
+    /// Lifetime of reactive paths to the root in TUs
+    pub hwmp_path_to_root_timeout: Option<u32>,
+    /// Interval of proactive path requests of the root in TUs
+    pub hwmp_root_interval: Option<u16>,
+    /// Minimum interval between proactive path replies in TUs
+    pub hwmp_confirmation_interval: Option<u16>,
+    /// Default mesh power mode for new peer links
+    pub power_mode: Option<Nl80211MeshPowerMode>,
+    /// Awake window duration in TUs
+    pub awake_window: Option<u16>,
+    /// Inactivity timeout of peer links in seconds
+    pub plink_timeout: Option<u32>,
+}
+
+impl MeshConfig {
+    pub(crate) fn attrs(&self) -> Result<Vec<Nlattr<Nl80211MeshconfParams, Buffer>>, SerError> {
+        use Nl80211MeshconfParams::*;
+
+        let mut attrs = Vec::new();
+        if let Some(value) = self.retry_timeout {
+            attrs.push(new_attr(MeshconfRetryTimeout, value)?);
+        }
+        if let Some(value) = self.confirm_timeout {
+            attrs.push(new_attr(MeshconfConfirmTimeout, value)?);
+        }
+        if let Some(value) = self.holding_timeout {
+            attrs.push(new_attr(MeshconfHoldingTimeout, value)?);
+        }
+        if let Some(value) = self.max_peer_links {
+            attrs.push(new_attr(MeshconfMaxPeerLinks, value)?);
+        }
+        if let Some(value) = self.max_retries {
+            attrs.push(new_attr(MeshconfMaxRetries, value)?);
+        }
+        if let Some(value) = self.ttl {
+            attrs.push(new_attr(MeshconfTtl, value)?);
+        }
+        if let Some(value) = self.auto_open_plinks {
+            attrs.push(new_attr(MeshconfAutoOpenPlinks, value as u8)?);
+        }
+        if let Some(value) = self.hwmp_max_preq_retries {
+            attrs.push(new_attr(MeshconfHwmpMaxPreqRetries, value)?);
+        }
+        if let Some(value) = self.path_refresh_time {
+            attrs.push(new_attr(MeshconfPathRefreshTime, value)?);
+        }
+        if let Some(value) = self.min_discovery_timeout {
+            attrs.push(new_attr(MeshconfMinDiscoveryTimeout, value)?);
+        }
+        if let Some(value) = self.hwmp_active_path_timeout {
+            attrs.push(new_attr(MeshconfHwmpActivePathTimeout, value)?);
+        }
+        if let Some(value) = self.hwmp_preq_min_interval {
+            attrs.push(new_attr(MeshconfHwmpPreqMinInterval, value)?);
+        }
+        if let Some(value) = self.hwmp_net_diameter_traversal_time {
+            attrs.push(new_attr(MeshconfHwmpNetDiamTrvsTime, value)?);
+        }
+        if let Some(value) = self.hwmp_root_mode {
+            attrs.push(new_attr(MeshconfHwmpRootmode, value)?);
+        }
+        if let Some(value) = self.element_ttl {
+            attrs.push(new_attr(MeshconfElementTtl, value)?);
+        }
+        if let Some(value) = self.hwmp_rann_interval {
+            attrs.push(new_attr(MeshconfHwmpRannInterval, value)?);
+        }
+        if let Some(value) = self.gate_announcements {
+            attrs.push(new_attr(MeshconfGateAnnouncements, value as u8)?);
+        }
+        if let Some(value) = self.hwmp_perr_min_interval {
+            attrs.push(new_attr(MeshconfHwmpPerrMinInterval, value)?);
+        }
+        if let Some(value) = self.forwarding {
+            attrs.push(new_attr(MeshconfForwarding, value as u8)?);
+        }
+        if let Some(value) = self.rssi_threshold {
+            attrs.push(new_attr(MeshconfRssiThreshold, value)?);
+        }
+        if let Some(value) = self.sync_offset_max_neighbor {
+            attrs.push(new_attr(MeshconfSyncOffsetMaxNeighbor, value)?);
+        }
+        if let Some(value) = self.ht_opmode {
+            attrs.push(new_attr(MeshconfHtOpmode, value)?);
+        }
+        if let Some(value) = self.hwmp_path_to_root_timeout {
+            attrs.push(new_attr(MeshconfHwmpPathToRootTimeout, value)?);
+        }
+        if let Some(value) = self.hwmp_root_interval {
+            attrs.push(new_attr(MeshconfHwmpRootInterval, value)?);
+        }
+        if let Some(value) = self.hwmp_confirmation_interval {
+            attrs.push(new_attr(MeshconfHwmpConfirmationInterval, value)?);
+        }
+        if let Some(value) = self.power_mode {
+            attrs.push(new_attr(MeshconfPowerMode, u32::from(u16::from(value)))?);
+        }
+        if let Some(value) = self.awake_window {
+            attrs.push(new_attr(MeshconfAwakeWindow, value)?);
+        }
+        if let Some(value) = self.plink_timeout {
+            attrs.push(new_attr(MeshconfPlinkTimeout, value)?);
+        }
+        Ok(attrs)
+    }
+}
+
+impl TryFrom<Attrs<'_, Nl80211Attr>> for MeshConfig {
+    type Error = DeError;
+
+    fn try_from(attrs: Attrs<'_, Nl80211Attr>) -> Result<Self, Self::Error> {
+        use Nl80211MeshconfParams::*;
+
+        let mut res = Self::default();
+        if let Some(config) = attrs.get_attribute(Nl80211Attr::AttrMeshConfig) {
+            let attrs = config.get_attr_handle::<Nl80211MeshconfParams>()?;
+            for attr in attrs.iter() {
+                match attr.nla_type.nla_type {
+                    MeshconfRetryTimeout => res.retry_timeout = Some(attr.get_payload_as()?),
+                    MeshconfConfirmTimeout => res.confirm_timeout = Some(attr.get_payload_as()?),
+                    MeshconfHoldingTimeout => res.holding_timeout = Some(attr.get_payload_as()?),
+                    MeshconfMaxPeerLinks => res.max_peer_links = Some(attr.get_payload_as()?),
+                    MeshconfMaxRetries => res.max_retries = Some(attr.get_payload_as()?),
+                    MeshconfTtl => res.ttl = Some(attr.get_payload_as()?),
+                    MeshconfAutoOpenPlinks => {
+                        res.auto_open_plinks = Some(attr.get_payload_as::<u8>()? != 0)
+                    }
+                    MeshconfHwmpMaxPreqRetries => {
+                        res.hwmp_max_preq_retries = Some(attr.get_payload_as()?)
+                    }
+                    MeshconfPathRefreshTime => res.path_refresh_time = Some(attr.get_payload_as()?),
+                    MeshconfMinDiscoveryTimeout => {
+                        res.min_discovery_timeout = Some(attr.get_payload_as()?)
+                    }
+                    MeshconfHwmpActivePathTimeout => {
+                        res.hwmp_active_path_timeout = Some(attr.get_payload_as()?)
+                    }
+                    MeshconfHwmpPreqMinInterval => {
+                        res.hwmp_preq_min_interval = Some(attr.get_payload_as()?)
+                    }
+                    MeshconfHwmpNetDiamTrvsTime => {
+                        res.hwmp_net_diameter_traversal_time = Some(attr.get_payload_as()?)
+                    }
+                    MeshconfHwmpRootmode => res.hwmp_root_mode = Some(attr.get_payload_as()?),
+                    MeshconfElementTtl => res.element_ttl = Some(attr.get_payload_as()?),
+                    MeshconfHwmpRannInterval => {
+                        res.hwmp_rann_interval = Some(attr.get_payload_as()?)
+                    }
+                    MeshconfGateAnnouncements => {
+                        res.gate_announcements = Some(attr.get_payload_as::<u8>()? != 0)
+                    }
+                    MeshconfHwmpPerrMinInterval => {
+                        res.hwmp_perr_min_interval = Some(attr.get_payload_as()?)
+                    }
+                    MeshconfForwarding => res.forwarding = Some(attr.get_payload_as::<u8>()? != 0),
+                    MeshconfRssiThreshold => res.rssi_threshold = Some(attr.get_payload_as()?),
+                    MeshconfSyncOffsetMaxNeighbor => {
+                        res.sync_offset_max_neighbor = Some(attr.get_payload_as()?)
+                    }
+                    MeshconfHtOpmode => res.ht_opmode = Some(attr.get_payload_as()?),
+                    MeshconfHwmpPathToRootTimeout => {
+                        res.hwmp_path_to_root_timeout = Some(attr.get_payload_as()?)
+                    }
+                    MeshconfHwmpRootInterval => {
+                        res.hwmp_root_interval = Some(attr.get_payload_as()?)
+                    }
+                    MeshconfHwmpConfirmationInterval => {
+                        res.hwmp_confirmation_interval = Some(attr.get_payload_as()?)
+                    }
+                    MeshconfPowerMode => {
+                        let mode: u32 = attr.get_payload_as()?;
+                        res.power_mode = Some(Nl80211MeshPowerMode::from(mode as u16));
+                    }
+                    MeshconfAwakeWindow => res.awake_window = Some(attr.get_payload_as()?),
+                    MeshconfPlinkTimeout => res.plink_timeout = Some(attr.get_payload_as()?),
+                    _ => (),
+                }
+            }
+        }
+        Ok(res)
+    }
+}
+
+/// Parameters used to join an 802.11s mesh
+///
+/// # Example
+///
+/// ```
+/// # use neli_wifi::{ChannelDef, JoinMeshParams, Nl80211ChanWidth};
+/// let params = JoinMeshParams::new(b"backhaul")
+///     .channel(ChannelDef::new(5180).with_width(Nl80211ChanWidth::ChanWidth80, 5210))
+///     .ttl(5)
+///     .forwarding(true);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JoinMeshParams {
+    mesh_id: Vec<u8>,
+    channel: Option<ChannelDef>,
+    basic_rates: Option<Vec<u8>>,
+    config: MeshConfig,
+    ie: Option<Vec<u8>>,
+    userspace_auth: bool,
+    userspace_ampe: bool,
+}
+
+impl JoinMeshParams {
+    /// Join the mesh identified by `mesh_id`
+    pub fn new(mesh_id: &[u8]) -> Self {
+        Self {
+            mesh_id: mesh_id.to_vec(),
+            channel: None,
+            basic_rates: None,
+            config: MeshConfig::default(),
+            ie: None,
+            userspace_auth: false,
+            userspace_ampe: false,
+        }
+    }
+
+    /// Operate on the given channel
+    pub fn channel(mut self, channel: ChannelDef) -> Self {
+        self.channel = Some(channel);
+        self
+    }
+
+    /// Basic rates in units of 500 kbps, requires the channel to be set
+    pub fn basic_rates(mut self, rates: &[u8]) -> Self {
+        self.basic_rates = Some(rates.to_vec());
+        self
+    }
+
+    /// Use the given mesh configuration, replacing any previously set parameters
+    pub fn config(mut self, config: MeshConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Time-to-live of mesh path selection frames
+    pub fn ttl(mut self, ttl: u8) -> Self {
+        self.config.ttl = Some(ttl);
+        self
+    }
+
+    /// HWMP root mode of this mesh point
+    pub fn hwmp_root_mode(mut self, mode: u8) -> Self {
+        self.config.hwmp_root_mode = Some(mode);
+        self
+    }
+
+    /// Whether frames are forwarded within the mesh
+    pub fn forwarding(mut self, forwarding: bool) -> Self {
+        self.config.forwarding = Some(forwarding);
+        self
+    }
+
+    /// RSSI threshold for peer links in dBm
+    pub fn rssi_threshold(mut self, threshold: i32) -> Self {
+        self.config.rssi_threshold = Some(threshold);
+        self
+    }
+
+    /// Default mesh power mode for new peer links
+    pub fn power_mode(mut self, mode: Nl80211MeshPowerMode) -> Self {
+        self.config.power_mode = Some(mode);
+        self
+    }
+
+    /// Extra information elements to add to beacons, e.g. the RSN element of a secured mesh
+    pub fn ie(mut self, ie: &[u8]) -> Self {
+        self.ie = Some(ie.to_vec());
+        self
+    }
+
+    /// Let userspace handle peer authentication (SAE)
+    pub fn userspace_auth(mut self, enabled: bool) -> Self {
+        self.userspace_auth = enabled;
+        self
+    }
+
+    /// Let userspace handle the authenticated mesh peering exchange (AMPE)
+    pub fn userspace_ampe(mut self, enabled: bool) -> Self {
+        self.userspace_ampe = enabled;
+        self
+    }
+
+    pub(crate) fn append_attrs(
+        &self,
+        attrs: &mut GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), SerError> {
+        attrs.push(new_attr(Nl80211Attr::AttrMeshId, self.mesh_id.as_slice())?);
+        if let Some(channel) = &self.channel {
+            channel.append_attrs(attrs)?;
+        }
+        if let Some(rates) = &self.basic_rates {
+            attrs.push(new_attr(Nl80211Attr::AttrBssBasicRates, rates.as_slice())?);
+        }
+
+        let config = self.config.attrs()?;
+        if !config.is_empty() {
+            attrs.push(new_nested_attr(Nl80211Attr::AttrMeshConfig, &config)?);
+        }
+
+        let mut setup = Vec::new();
+        if let Some(ie) = &self.ie {
+            setup.push(new_attr(
+                Nl80211MeshSetupParams::MeshSetupIe,
+                ie.as_slice(),
+            )?);
+        }
+        if self.userspace_auth {
+            setup.push(new_attr(
+                Nl80211MeshSetupParams::MeshSetupUserspaceAuth,
+                (),
+            )?);
+        }
+        if self.userspace_ampe {
+            setup.push(new_attr(
+                Nl80211MeshSetupParams::MeshSetupUserspaceAmpe,
+                (),
+            )?);
+        }
+        if !setup.is_empty() {
+            attrs.push(new_nested_attr(Nl80211Attr::AttrMeshSetup, &setup)?);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_mesh_config {
+    use super::*;
+    use crate::attr::Nl80211Attr::*;
+    use neli::attr::AttrHandle;
+    use neli::genl::{AttrType, Nlattr};
+    use neli::types::Buffer;
+
+    fn new_attr(t: Nl80211Attr, d: Vec<u8>) -> Nlattr<Nl80211Attr, Buffer> {
+        Nlattr {
+            nla_len: (4 + d.len()) as _,
+            nla_type: AttrType {
+                nla_nested: false,
+                nla_network_order: true,
+                nla_type: t,
+            },
+            nla_payload: d.into(),
+        }
+    }
+
+    #[test]
+    fn test_parser() {
+        let handler = vec![
+            new_attr(AttrGeneration, vec![2, 0, 0, 0]),
+            new_attr(AttrIfindex, vec![5, 0, 0, 0]),
+            new_attr(
+                AttrMeshConfig,
+                vec![
+                    6, 0, 1, 0, 100, 0, 0, 0, 6, 0, 4, 0, 99, 0, 0, 0, 5, 0, 5, 0, 3, 0, 0, 0, 5,
+                    0, 6, 0, 31, 0, 0, 0, 5, 0, 7, 0, 1, 0, 0, 0, 8, 0, 9, 0, 232, 3, 0, 0, 5, 0,
+                    14, 0, 0, 0, 0, 0, 5, 0, 19, 0, 1, 0, 0, 0, 8, 0, 20, 0, 172, 255, 255, 255, 8,
+                    0, 26, 0, 1, 0, 0, 0,
+                ],
+            ),
+        ];
+
+        let config: MeshConfig = AttrHandle::new(handler.into_iter().collect())
+            .try_into()
+            .unwrap();
+        let expected_config = MeshConfig {
+            retry_timeout: Some(100),
+            max_peer_links: Some(99),
+            max_retries: Some(3),
+            ttl: Some(31),
+            auto_open_plinks: Some(true),
+            path_refresh_time: Some(1000),
+            hwmp_root_mode: Some(0),
+            forwarding: Some(true),
+            rssi_threshold: Some(-84),
+            power_mode: Some(Nl80211MeshPowerMode::MeshPowerActive),
+            ..Default::default()
+        };
+
+        assert_eq!(config, expected_config)
+    }
+}
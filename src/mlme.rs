@@ -0,0 +1,405 @@
+use crate::attr::{new_attr, Attrs, Nl80211Attr, Nl80211AuthType, Nl80211Mfp, Nl80211WpaVersions};
+
+use neli::attr::Attribute;
+use neli::err::{DeError, SerError};
+use neli::types::{Buffer, GenlBuffer};
+
+/// Parameters of an authentication request
+///
+/// # Example
+///
+/// ```
+/// # use neli_wifi::{AuthenticateParams, Nl80211AuthType};
+/// let params = AuthenticateParams::new(
+///     &[0x02, 0, 0, 0, 1, 0],
+///     2412,
+///     b"home",
+///     Nl80211AuthType::AuthtypeOpenSystem,
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticateParams {
+    bssid: Vec<u8>,
+    frequency: u32,
+    ssid: Vec<u8>,
+    auth_type: Nl80211AuthType,
+    ie: Option<Vec<u8>>,
+    sae_data: Option<Vec<u8>>,
+    local_state_change: bool,
+}
+
+impl AuthenticateParams {
+    /// Authenticate with the BSS `bssid` of the network `ssid` on `frequency` (MHz)
+    pub fn new(bssid: &[u8], frequency: u32, ssid: &[u8], auth_type: Nl80211AuthType) -> Self {
+        Self {
+            bssid: bssid.to_vec(),
+            frequency,
+            ssid: ssid.to_vec(),
+            auth_type,
+            ie: None,
+            sae_data: None,
+            local_state_change: false,
+        }
+    }
+
+    /// Extra information elements, e.g. the MDE and FTE for FT authentication
+    pub fn ie(mut self, ie: &[u8]) -> Self {
+        self.ie = Some(ie.to_vec());
+        self
+    }
+
+    /// SAE (or FILS) data of the authentication frame, starting with the transaction number
+    pub fn sae_data(mut self, data: &[u8]) -> Self {
+        self.sae_data = Some(data.to_vec());
+        self
+    }
+
+    /// Only update the local state, no frame is sent to the AP
+    pub fn local_state_change(mut self, local_state_change: bool) -> Self {
+        self.local_state_change = local_state_change;
+        self
+    }
+
+    pub(crate) fn append_attrs(
+        &self,
+        attrs: &mut GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), SerError> {
+        attrs.push(new_attr(Nl80211Attr::AttrMac, self.bssid.as_slice())?);
+        attrs.push(new_attr(Nl80211Attr::AttrWiphyFreq, self.frequency)?);
+        attrs.push(new_attr(Nl80211Attr::AttrSsid, self.ssid.as_slice())?);
+        attrs.push(new_attr(
+            Nl80211Attr::AttrAuthType,
+            u32::from(u16::from(self.auth_type)),
+        )?);
+        if let Some(ie) = &self.ie {
+            attrs.push(new_attr(Nl80211Attr::AttrIe, ie.as_slice())?);
+        }
+        if let Some(data) = &self.sae_data {
+            attrs.push(new_attr(Nl80211Attr::AttrSaeData, data.as_slice())?);
+        }
+        if self.local_state_change {
+            attrs.push(new_attr(Nl80211Attr::AttrLocalStateChange, ())?);
+        }
+        Ok(())
+    }
+}
+
+/// Parameters of an association request
+///
+/// Cipher and AKM suites are given as selectors, like in [`ConnectParams`](crate::ConnectParams).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssociateParams {
+    bssid: Vec<u8>,
+    frequency: u32,
+    ssid: Vec<u8>,
+    ie: Option<Vec<u8>>,
+    prev_bssid: Option<Vec<u8>>,
+    wpa_versions: Option<u32>,
+    pairwise_ciphers: Option<Vec<u32>>,
+    group_cipher: Option<u32>,
+    akm_suites: Option<Vec<u32>>,
+    mfp: Option<Nl80211Mfp>,
+    control_port: bool,
+    control_port_ethertype: Option<u16>,
+    control_port_no_encrypt: bool,
+    control_port_over_nl80211: bool,
+    disable_ht: bool,
+    ht_capability: Option<(Vec<u8>, Vec<u8>)>,
+    disable_vht: bool,
+    vht_capability: Option<(Vec<u8>, Vec<u8>)>,
+}
+
+impl AssociateParams {
+    /// Associate with the BSS `bssid` of the network `ssid` on `frequency` (MHz)
+    pub fn new(bssid: &[u8], frequency: u32, ssid: &[u8]) -> Self {
+        Self {
+            bssid: bssid.to_vec(),
+            frequency,
+            ssid: ssid.to_vec(),
+            ie: None,
+            prev_bssid: None,
+            wpa_versions: None,
+            pairwise_ciphers: None,
+            group_cipher: None,
+            akm_suites: None,
+            mfp: None,
+            control_port: false,
+            control_port_ethertype: None,
+            control_port_no_encrypt: false,
+            control_port_over_nl80211: false,
+            disable_ht: false,
+            ht_capability: None,
+            disable_vht: false,
+            vht_capability: None,
+        }
+    }
+
+    /// Extra information elements to add to the (re)association request, e.g. the RSNE
+    pub fn ie(mut self, ie: &[u8]) -> Self {
+        self.ie = Some(ie.to_vec());
+        self
+    }
+
+    /// Send a reassociation request, `bssid` being the BSS we are currently associated with
+    pub fn prev_bssid(mut self, bssid: &[u8]) -> Self {
+        self.prev_bssid = Some(bssid.to_vec());
+        self
+    }
+
+    /// WPA versions in use
+    pub fn wpa_versions(mut self, versions: &[Nl80211WpaVersions]) -> Self {
+        self.wpa_versions = Some(versions.iter().map(|v| u32::from(u16::from(v))).sum());
+        self
+    }
+
+    /// Pairwise cipher suites
+    pub fn pairwise_ciphers(mut self, ciphers: &[u32]) -> Self {
+        self.pairwise_ciphers = Some(ciphers.to_vec());
+        self
+    }
+
+    /// Group cipher suite
+    pub fn group_cipher(mut self, cipher: u32) -> Self {
+        self.group_cipher = Some(cipher);
+        self
+    }
+
+    /// AKM suites
+    pub fn akm_suites(mut self, suites: &[u32]) -> Self {
+        self.akm_suites = Some(suites.to_vec());
+        self
+    }
+
+    /// Management frame protection
+    pub fn mfp(mut self, mfp: Nl80211Mfp) -> Self {
+        self.mfp = Some(mfp);
+        self
+    }
+
+    /// Block data frames other than the control port protocol until the port is authorized
+    pub fn control_port(mut self, control_port: bool) -> Self {
+        self.control_port = control_port;
+        self
+    }
+
+    /// Ethertype of the control port protocol (EAPOL if not set)
+    pub fn control_port_ethertype(mut self, ethertype: u16) -> Self {
+        self.control_port_ethertype = Some(ethertype);
+        self
+    }
+
+    /// Send control port frames unencrypted
+    pub fn control_port_no_encrypt(mut self, no_encrypt: bool) -> Self {
+        self.control_port_no_encrypt = no_encrypt;
+        self
+    }
+
+    /// Exchange control port frames over nl80211 instead of the network interface
+    pub fn control_port_over_nl80211(mut self, over_nl80211: bool) -> Self {
+        self.control_port_over_nl80211 = over_nl80211;
+        self
+    }
+
+    /// Do not use HT (802.11n)
+    pub fn disable_ht(mut self, disable: bool) -> Self {
+        self.disable_ht = disable;
+        self
+    }
+
+    /// Override the bits of the HT capabilities selected by `mask` with `capability`
+    pub fn ht_capability(mut self, capability: &[u8], mask: &[u8]) -> Self {
+        self.ht_capability = Some((capability.to_vec(), mask.to_vec()));
+        self
+    }
+
+    /// Do not use VHT (802.11ac)
+    pub fn disable_vht(mut self, disable: bool) -> Self {
+        self.disable_vht = disable;
+        self
+    }
+
+    /// Override the bits of the VHT capabilities selected by `mask` with `capability`
+    pub fn vht_capability(mut self, capability: &[u8], mask: &[u8]) -> Self {
+        self.vht_capability = Some((capability.to_vec(), mask.to_vec()));
+        self
+    }
+
+    pub(crate) fn append_attrs(
+        &self,
+        attrs: &mut GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), SerError> {
+        attrs.push(new_attr(Nl80211Attr::AttrMac, self.bssid.as_slice())?);
+        attrs.push(new_attr(Nl80211Attr::AttrWiphyFreq, self.frequency)?);
+        attrs.push(new_attr(Nl80211Attr::AttrSsid, self.ssid.as_slice())?);
+        if let Some(ie) = &self.ie {
+            attrs.push(new_attr(Nl80211Attr::AttrIe, ie.as_slice())?);
+        }
+        if let Some(bssid) = &self.prev_bssid {
+            attrs.push(new_attr(Nl80211Attr::AttrPrevBssid, bssid.as_slice())?);
+        }
+        if let Some(versions) = self.wpa_versions {
+            attrs.push(new_attr(Nl80211Attr::AttrWpaVersions, versions)?);
+        }
+        if let Some(ciphers) = &self.pairwise_ciphers {
+            attrs.push(new_attr(
+                Nl80211Attr::AttrCipherSuitesPairwise,
+                ciphers.clone(),
+            )?);
+        }
+        if let Some(cipher) = self.group_cipher {
+            attrs.push(new_attr(Nl80211Attr::AttrCipherSuiteGroup, cipher)?);
+        }
+        if let Some(suites) = &self.akm_suites {
+            attrs.push(new_attr(Nl80211Attr::AttrAkmSuites, suites.clone())?);
+        }
+        if let Some(mfp) = self.mfp {
+            attrs.push(new_attr(
+                Nl80211Attr::AttrUseMfp,
+                u32::from(u16::from(mfp)),
+            )?);
+        }
+        if self.control_port {
+            attrs.push(new_attr(Nl80211Attr::AttrControlPort, ())?);
+        }
+        if let Some(ethertype) = self.control_port_ethertype {
+            attrs.push(new_attr(Nl80211Attr::AttrControlPortEthertype, ethertype)?);
+        }
+        if self.control_port_no_encrypt {
+            attrs.push(new_attr(Nl80211Attr::AttrControlPortNoEncrypt, ())?);
+        }
+        if self.control_port_over_nl80211 {
+            attrs.push(new_attr(Nl80211Attr::AttrControlPortOverNl80211, ())?);
+        }
+        if self.disable_ht {
+            attrs.push(new_attr(Nl80211Attr::AttrDisableHt, ())?);
+        }
+        if let Some((capability, mask)) = &self.ht_capability {
+            attrs.push(new_attr(
+                Nl80211Attr::AttrHtCapability,
+                capability.as_slice(),
+            )?);
+            attrs.push(new_attr(
+                Nl80211Attr::AttrHtCapabilityMask,
+                mask.as_slice(),
+            )?);
+        }
+        if self.disable_vht {
+            attrs.push(new_attr(Nl80211Attr::AttrDisableVht, ())?);
+        }
+        if let Some((capability, mask)) = &self.vht_capability {
+            attrs.push(new_attr(
+                Nl80211Attr::AttrVhtCapability,
+                capability.as_slice(),
+            )?);
+            attrs.push(new_attr(
+                Nl80211Attr::AttrVhtCapabilityMask,
+                mask.as_slice(),
+            )?);
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of an authentication or association request
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MlmeResult {
+    /// The AP answered with the given authentication or (re)association response frame
+    Response(Vec<u8>),
+    /// The AP did not answer
+    TimedOut,
+}
+
+impl MlmeResult {
+    /// Status code of the response frame
+    pub fn status_code(&self) -> Option<u16> {
+        let frame = match self {
+            MlmeResult::Response(frame) => frame,
+            MlmeResult::TimedOut => return None,
+        };
+        // Authentication frames start with the algorithm and the transaction number,
+        // (re)association responses with the capabilities
+        let offset = match frame.first()? & 0xfc {
+            0xb0 => 28,
+            0x10 | 0x30 => 26,
+            _ => return None,
+        };
+        let status = frame.get(offset..offset + 2)?;
+        Some(u16::from_le_bytes([status[0], status[1]]))
+    }
+}
+
+impl TryFrom<Attrs<'_, Nl80211Attr>> for MlmeResult {
+    type Error = DeError;
+
+    fn try_from(attrs: Attrs<'_, Nl80211Attr>) -> Result<Self, Self::Error> {
+        let mut res = None;
+        for attr in attrs.iter() {
+            match attr.nla_type.nla_type {
+                Nl80211Attr::AttrFrame => {
+                    res = Some(MlmeResult::Response(attr.get_payload_as_with_len()?));
+                }
+                Nl80211Attr::AttrTimedOut => return Ok(MlmeResult::TimedOut),
+                _ => (),
+            }
+        }
+        res.ok_or_else(|| DeError::new("Missing response frame"))
+    }
+}
+
+#[cfg(test)]
+mod test_mlme_result {
+    use super::*;
+    use crate::attr::Nl80211Attr::*;
+    use neli::attr::AttrHandle;
+    use neli::genl::{AttrType, Nlattr};
+    use neli::types::Buffer;
+
+    fn new_attr(t: Nl80211Attr, d: Vec<u8>) -> Nlattr<Nl80211Attr, Buffer> {
+        Nlattr {
+            nla_len: (4 + d.len()) as _,
+            nla_type: AttrType {
+                nla_nested: false,
+                nla_network_order: true,
+                nla_type: t,
+            },
+            nla_payload: d.into(),
+        }
+    }
+
+    #[test]
+    fn test_parser() {
+        let frame = vec![
+            176, 0, 58, 1, 2, 0, 0, 0, 0, 0, 2, 0, 0, 0, 1, 0, 2, 0, 0, 0, 1, 0, 48, 0, 0, 0, 2, 0,
+            17, 0,
+        ];
+        let handler = vec![
+            new_attr(AttrWiphy, vec![0, 0, 0, 0]),
+            new_attr(AttrIfindex, vec![3, 0, 0, 0]),
+            new_attr(AttrFrame, frame.clone()),
+        ];
+
+        let result: MlmeResult = AttrHandle::new(handler.into_iter().collect())
+            .try_into()
+            .unwrap();
+
+        assert_eq!(result, MlmeResult::Response(frame));
+        assert_eq!(result.status_code(), Some(17));
+    }
+
+    #[test]
+    fn test_timed_out() {
+        let handler = vec![
+            new_attr(AttrIfindex, vec![3, 0, 0, 0]),
+            new_attr(AttrTimedOut, vec![]),
+            new_attr(AttrMac, vec![2, 0, 0, 0, 1, 0]),
+        ];
+
+        let result: MlmeResult = AttrHandle::new(handler.into_iter().collect())
+            .try_into()
+            .unwrap();
+
+        assert_eq!(result, MlmeResult::TimedOut);
+        assert_eq!(result.status_code(), None);
+    }
+}
@@ -0,0 +1,154 @@
+use crate::attr::{Attrs, Nl80211Attr, Nl80211MpathFlags, Nl80211MpathInfo};
+
+use neli::attr::Attribute;
+use neli::err::DeError;
+
+/// A struct representing an entry of the mesh path table
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MeshPath {
+    /// Destination MAC address
+    pub destination: Option<Vec<u8>>,
+    /// MAC address of the next hop (or of the mesh proxy for proxy paths)
+    pub next_hop: Option<Vec<u8>>,
+    /// Number of queued frames for this destination
+    pub frame_qlen: Option<u32>,
+    /// Destination sequence number
+    pub sn: Option<u32>,
+    /// Metric (cost) of this path
+    pub metric: Option<u32>,
+    /// Expiration time in ms
+    pub expiry_time: Option<u32>,
+    /// Mesh path flags
+    pub flags: Option<Vec<Nl80211MpathFlags>>,
+    /// Total path discovery timeout in ms
+    pub discovery_timeout: Option<u32>,
+    /// Mesh path discovery retries
+    pub discovery_retries: Option<u8>,
+    /// Hop count to destination
+    pub hop_count: Option<u8>,
+    /// Total number of path changes to destination
+    pub path_change_count: Option<u32>,
+}
+
+impl TryFrom<Attrs<'_, Nl80211Attr>> for MeshPath {
+    type Error = DeError;
+
+    fn try_from(attrs: Attrs<'_, Nl80211Attr>) -> Result<Self, Self::Error> {
+        let mut res = Self::default();
+        for attr in attrs.iter() {
+            match attr.nla_type.nla_type {
+                Nl80211Attr::AttrMac => {
+                    res.destination = Some(attr.get_payload_as_with_len()?);
+                }
+                Nl80211Attr::AttrMpathNextHop => {
+                    res.next_hop = Some(attr.get_payload_as_with_len()?);
+                }
+                Nl80211Attr::AttrMpathInfo => {
+                    let attrs = attr.get_attr_handle::<Nl80211MpathInfo>()?;
+                    for attr in attrs.iter() {
+                        match attr.nla_type.nla_type {
+                            Nl80211MpathInfo::MpathInfoFrameQlen => {
+                                res.frame_qlen = Some(attr.get_payload_as()?)
+                            }
+                            Nl80211MpathInfo::MpathInfoSn => res.sn = Some(attr.get_payload_as()?),
+                            Nl80211MpathInfo::MpathInfoMetric => {
+                                res.metric = Some(attr.get_payload_as()?)
+                            }
+                            Nl80211MpathInfo::MpathInfoExptime => {
+                                res.expiry_time = Some(attr.get_payload_as()?)
+                            }
+                            Nl80211MpathInfo::MpathInfoFlags => {
+                                let flags: u8 = attr.get_payload_as()?;
+                                res.flags = Some(
+                                    (0..8)
+                                        .map(|bit| 1 << bit)
+                                        .filter(|flag| flags & flag != 0)
+                                        .map(|flag| Nl80211MpathFlags::from(flag as u16))
+                                        .collect(),
+                                );
+                            }
+                            Nl80211MpathInfo::MpathInfoDiscoveryTimeout => {
+                                res.discovery_timeout = Some(attr.get_payload_as()?)
+                            }
+                            Nl80211MpathInfo::MpathInfoDiscoveryRetries => {
+                                res.discovery_retries = Some(attr.get_payload_as()?)
+                            }
+                            Nl80211MpathInfo::MpathInfoHopCount => {
+                                res.hop_count = Some(attr.get_payload_as()?)
+                            }
+                            Nl80211MpathInfo::MpathInfoPathChange => {
+                                res.path_change_count = Some(attr.get_payload_as()?)
+                            }
+                            _ => (),
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod test_mesh_path {
+    use super::*;
+    use crate::attr::Nl80211Attr::*;
+    use neli::attr::AttrHandle;
+    use neli::genl::{AttrType, Nlattr};
+    use neli::types::Buffer;
+
+    fn new_attr(t: Nl80211Attr, d: Vec<u8>) -> Nlattr<Nl80211Attr, Buffer> {
+        Nlattr {
+            nla_len: (4 + d.len()) as _,
+            nla_type: AttrType {
+                nla_nested: false,
+                nla_network_order: true,
+                nla_type: t,
+            },
+            nla_payload: d.into(),
+        }
+    }
+
+    #[test]
+    fn test_parser() {
+        let handler = vec![
+            new_attr(AttrIfindex, vec![5, 0, 0, 0]),
+            new_attr(AttrMac, vec![2, 0, 0, 0, 1, 0]),
+            new_attr(AttrMpathNextHop, vec![2, 0, 0, 0, 2, 0]),
+            new_attr(AttrGeneration, vec![14, 0, 0, 0]),
+            new_attr(
+                AttrMpathInfo,
+                vec![
+                    8, 0, 1, 0, 0, 0, 0, 0, 8, 0, 2, 0, 23, 0, 0, 0, 8, 0, 3, 0, 170, 1, 0, 0, 8,
+                    0, 4, 0, 136, 19, 0, 0, 5, 0, 5, 0, 21, 0, 0, 0, 8, 0, 6, 0, 2, 2, 0, 0, 5, 0,
+                    7, 0, 0, 0, 0, 0, 5, 0, 8, 0, 2, 0, 0, 0, 8, 0, 9, 0, 3, 0, 0, 0,
+                ],
+            ),
+        ];
+
+        let mesh_path: MeshPath = AttrHandle::new(handler.into_iter().collect())
+            .try_into()
+            .unwrap();
+        let expected_mesh_path = MeshPath {
+            destination: Some(vec![2, 0, 0, 0, 1, 0]),
+            next_hop: Some(vec![2, 0, 0, 0, 2, 0]),
+            frame_qlen: Some(0),
+            sn: Some(23),
+            metric: Some(426),
+            expiry_time: Some(5000),
+            flags: Some(vec![
+                Nl80211MpathFlags::MpathFlagActive,
+                Nl80211MpathFlags::MpathFlagSnValid,
+                Nl80211MpathFlags::MpathFlagResolved,
+            ]),
+            discovery_timeout: Some(514),
+            discovery_retries: Some(0),
+            hop_count: Some(2),
+            path_change_count: Some(3),
+        };
+
+        assert_eq!(mesh_path, expected_mesh_path)
+    }
+}
@@ -0,0 +1,180 @@
+use crate::attr::{new_attr, Nl80211Attr};
+
+use neli::err::SerError;
+use neli::types::{Buffer, GenlBuffer};
+
+/// A PMKSA cache entry, identified either by a BSSID or by an SSID and a FILS cache ID
+///
+/// Only the identity of the entry is used when deleting it.
+///
+/// # Example
+///
+/// ```
+/// # use neli_wifi::PmksaParams;
+/// let entry = PmksaParams::new(&[0; 16])
+///     .bssid(&[0x02, 0, 0, 0, 1, 0])
+///     .pmk(&[0; 32])
+///     .lifetime(43200);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PmksaParams {
+    pmkid: Vec<u8>,
+    bssid: Option<Vec<u8>>,
+    ssid: Option<Vec<u8>>,
+    fils_cache_id: Option<Vec<u8>>,
+    pmk: Option<Vec<u8>>,
+    lifetime: Option<u32>,
+    reauth_threshold: Option<u8>,
+}
+
+impl PmksaParams {
+    /// Entry for the given PMKID
+    pub fn new(pmkid: &[u8]) -> Self {
+        Self {
+            pmkid: pmkid.to_vec(),
+            bssid: None,
+            ssid: None,
+            fils_cache_id: None,
+            pmk: None,
+            lifetime: None,
+            reauth_threshold: None,
+        }
+    }
+
+    /// BSSID of the AP the PMKSA was established with
+    pub fn bssid(mut self, bssid: &[u8]) -> Self {
+        self.bssid = Some(bssid.to_vec());
+        self
+    }
+
+    /// SSID and FILS cache identifier of the network the PMKSA was established with
+    pub fn fils_cache_id(mut self, ssid: &[u8], cache_id: &[u8]) -> Self {
+        self.ssid = Some(ssid.to_vec());
+        self.fils_cache_id = Some(cache_id.to_vec());
+        self
+    }
+
+    /// PMK, for drivers that offload the 4-way handshake or FILS
+    pub fn pmk(mut self, pmk: &[u8]) -> Self {
+        self.pmk = Some(pmk.to_vec());
+        self
+    }
+
+    /// Lifetime of the PMK in seconds
+    pub fn lifetime(mut self, lifetime: u32) -> Self {
+        self.lifetime = Some(lifetime);
+        self
+    }
+
+    /// Percentage of the lifetime after which the PMK should be refreshed
+    pub fn reauth_threshold(mut self, threshold: u8) -> Self {
+        self.reauth_threshold = Some(threshold);
+        self
+    }
+
+    pub(crate) fn append_id_attrs(
+        &self,
+        attrs: &mut GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), SerError> {
+        attrs.push(new_attr(Nl80211Attr::AttrPmkid, self.pmkid.as_slice())?);
+        if let Some(bssid) = &self.bssid {
+            attrs.push(new_attr(Nl80211Attr::AttrMac, bssid.as_slice())?);
+        }
+        if let Some(ssid) = &self.ssid {
+            attrs.push(new_attr(Nl80211Attr::AttrSsid, ssid.as_slice())?);
+        }
+        if let Some(cache_id) = &self.fils_cache_id {
+            attrs.push(new_attr(Nl80211Attr::AttrFilsCacheId, cache_id.as_slice())?);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn append_attrs(
+        &self,
+        attrs: &mut GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), SerError> {
+        self.append_id_attrs(attrs)?;
+        if let Some(pmk) = &self.pmk {
+            attrs.push(new_attr(Nl80211Attr::AttrPmk, pmk.as_slice())?);
+        }
+        if let Some(lifetime) = self.lifetime {
+            attrs.push(new_attr(Nl80211Attr::AttrPmkLifetime, lifetime)?);
+        }
+        if let Some(threshold) = self.reauth_threshold {
+            attrs.push(new_attr(Nl80211Attr::AttrPmkReauthThreshold, threshold)?);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_pmksa_params {
+    use super::*;
+    use neli::ToBytes;
+    use std::io::Cursor;
+
+    const PMKID: [u8; 16] = [0x11; 16];
+
+    fn to_bytes(attrs: GenlBuffer<Nl80211Attr, Buffer>) -> Vec<u8> {
+        let mut bytes = Cursor::new(Vec::new());
+        attrs.to_bytes(&mut bytes).unwrap();
+        bytes.into_inner()
+    }
+
+    #[test]
+    fn test_bssid_entry() {
+        let entry = PmksaParams::new(&PMKID)
+            .bssid(&[0x02, 0, 0, 0, 1, 0])
+            .pmk(&[0x22; 32])
+            .lifetime(43200)
+            .reauth_threshold(70);
+        let mut attrs = GenlBuffer::new();
+        entry.append_attrs(&mut attrs).unwrap();
+
+        let expected = [
+            vec![20, 0, 85, 0], // PMKID
+            PMKID.to_vec(),
+            vec![10, 0, 6, 0, 2, 0, 0, 0, 1, 0, 0, 0], // BSSID
+            vec![36, 0, 254, 0],                       // PMK
+            vec![0x22; 32],
+            vec![8, 0, 31, 1, 192, 168, 0, 0], // lifetime
+            vec![5, 0, 32, 1, 70, 0, 0, 0],    // reauthentication threshold
+        ]
+        .concat();
+        assert_eq!(to_bytes(attrs), expected);
+    }
+
+    #[test]
+    fn test_fils_entry() {
+        let entry = PmksaParams::new(&PMKID).fils_cache_id(b"test", &[0x12, 0x34]);
+        let mut attrs = GenlBuffer::new();
+        entry.append_attrs(&mut attrs).unwrap();
+
+        let expected = [
+            vec![20, 0, 85, 0], // PMKID
+            PMKID.to_vec(),
+            vec![8, 0, 52, 0, b't', b'e', b's', b't'], // SSID
+            vec![6, 0, 253, 0, 0x12, 0x34, 0, 0],      // FILS cache ID
+        ]
+        .concat();
+        assert_eq!(to_bytes(attrs), expected);
+    }
+
+    #[test]
+    fn test_id_attrs() {
+        let entry = PmksaParams::new(&PMKID)
+            .bssid(&[0x02, 0, 0, 0, 1, 0])
+            .pmk(&[0x22; 32])
+            .lifetime(43200);
+        let mut attrs = GenlBuffer::new();
+        entry.append_id_attrs(&mut attrs).unwrap();
+
+        let expected = [
+            vec![20, 0, 85, 0], // PMKID
+            PMKID.to_vec(),
+            vec![10, 0, 6, 0, 2, 0, 0, 0, 1, 0, 0, 0], // BSSID
+        ]
+        .concat();
+        assert_eq!(to_bytes(attrs), expected);
+    }
+}
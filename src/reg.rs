@@ -0,0 +1,170 @@
+use crate::attr::{Attrs, Nl80211Attr, Nl80211DfsRegions, Nl80211RegRuleAttr, Nl80211RegRuleFlags};
+
+use neli::attr::Attribute;
+use neli::err::DeError;
+
+/// A struct representing a regulatory domain
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RegDomain {
+    /// Wiphy index, not set for the global domain
+    pub wiphy: Option<u32>,
+    /// ISO/IEC 3166-1 alpha2 country code, "00" for the world domain
+    pub alpha2: Option<String>,
+    /// DFS region
+    pub dfs_region: Option<Nl80211DfsRegions>,
+    /// The domain is managed by the driver of the wiphy
+    pub self_managed: bool,
+    /// Regulatory rules
+    pub rules: Option<Vec<RegRule>>,
+}
+
+/// A struct representing a regulatory rule
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RegRule {
+    /// Restrictions of the rule
+    pub flags: Option<Vec<Nl80211RegRuleFlags>>,
+    /// Start of the frequency range (KHz)
+    pub start_freq: Option<u32>,
+    /// End of the frequency range (KHz)
+    pub end_freq: Option<u32>,
+    /// Maximum bandwidth (KHz)
+    pub max_bandwidth: Option<u32>,
+    /// Maximum antenna gain (mBi)
+    pub max_antenna_gain: Option<u32>,
+    /// Maximum EIRP (mBm)
+    pub max_eirp: Option<u32>,
+    /// DFS CAC time (ms)
+    pub dfs_cac_time: Option<u32>,
+}
+
+impl TryFrom<Attrs<'_, Nl80211Attr>> for RegDomain {
+    type Error = DeError;
+
+    fn try_from(attrs: Attrs<'_, Nl80211Attr>) -> Result<Self, Self::Error> {
+        let mut res = Self::default();
+        for attr in attrs.iter() {
+            match attr.nla_type.nla_type {
+                Nl80211Attr::AttrWiphy => res.wiphy = Some(attr.get_payload_as()?),
+                Nl80211Attr::AttrRegAlpha2 => res.alpha2 = Some(attr.get_payload_as_with_len()?),
+                Nl80211Attr::AttrDfsRegion => {
+                    let region: u8 = attr.get_payload_as()?;
+                    res.dfs_region = Some(Nl80211DfsRegions::from(region as u16));
+                }
+                Nl80211Attr::AttrWiphySelfManagedReg => res.self_managed = true,
+                Nl80211Attr::AttrRegRules => {
+                    let rules = attr.get_attr_handle::<u16>()?;
+                    res.rules = Some(
+                        rules
+                            .iter()
+                            .map(|rule| rule.get_attr_handle::<Nl80211RegRuleAttr>()?.try_into())
+                            .collect::<Result<_, _>>()?,
+                    );
+                }
+                _ => (),
+            }
+        }
+        Ok(res)
+    }
+}
+
+impl TryFrom<Attrs<'_, Nl80211RegRuleAttr>> for RegRule {
+    type Error = DeError;
+
+    fn try_from(attrs: Attrs<'_, Nl80211RegRuleAttr>) -> Result<Self, Self::Error> {
+        let mut res = Self::default();
+        for attr in attrs.iter() {
+            match attr.nla_type.nla_type {
+                Nl80211RegRuleAttr::AttrRegRuleFlags => {
+                    let flags: u32 = attr.get_payload_as()?;
+                    res.flags = Some(
+                        (0..32)
+                            .map(|bit| 1 << bit)
+                            .filter(|flag| flags & flag != 0)
+                            .map(Nl80211RegRuleFlags::from)
+                            .collect(),
+                    );
+                }
+                Nl80211RegRuleAttr::AttrFreqRangeStart => {
+                    res.start_freq = Some(attr.get_payload_as()?)
+                }
+                Nl80211RegRuleAttr::AttrFreqRangeEnd => res.end_freq = Some(attr.get_payload_as()?),
+                Nl80211RegRuleAttr::AttrFreqRangeMaxBw => {
+                    res.max_bandwidth = Some(attr.get_payload_as()?)
+                }
+                Nl80211RegRuleAttr::AttrPowerRuleMaxAntGain => {
+                    res.max_antenna_gain = Some(attr.get_payload_as()?)
+                }
+                Nl80211RegRuleAttr::AttrPowerRuleMaxEirp => {
+                    res.max_eirp = Some(attr.get_payload_as()?)
+                }
+                Nl80211RegRuleAttr::AttrDfsCacTime => {
+                    res.dfs_cac_time = Some(attr.get_payload_as()?)
+                }
+                _ => (),
+            }
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod test_reg_domain {
+    use super::*;
+    use crate::attr::Nl80211Attr::*;
+    use neli::attr::AttrHandle;
+    use neli::genl::{AttrType, Nlattr};
+    use neli::types::Buffer;
+
+    fn new_attr(t: Nl80211Attr, d: Vec<u8>) -> Nlattr<Nl80211Attr, Buffer> {
+        Nlattr {
+            nla_len: (4 + d.len()) as _,
+            nla_type: AttrType {
+                nla_nested: false,
+                nla_network_order: true,
+                nla_type: t,
+            },
+            nla_payload: d.into(),
+        }
+    }
+
+    #[test]
+    fn test_parser() {
+        let handler = vec![
+            new_attr(AttrWiphy, vec![1, 0, 0, 0]),
+            new_attr(AttrRegAlpha2, vec![70, 82, 0]),
+            new_attr(AttrDfsRegion, vec![2]),
+            new_attr(AttrWiphySelfManagedReg, vec![]),
+            new_attr(
+                AttrRegRules,
+                vec![
+                    36, 0, 1, 0, 8, 0, 1, 0, 16, 0, 1, 0, 8, 0, 2, 0, 80, 197, 83, 0, 8, 0, 3, 0,
+                    176, 32, 87, 0, 8, 0, 4, 0, 0, 113, 2, 0,
+                ],
+            ),
+        ];
+
+        let domain: RegDomain = AttrHandle::new(handler.into_iter().collect())
+            .try_into()
+            .unwrap();
+        let expected_domain = RegDomain {
+            wiphy: Some(1),
+            alpha2: Some("FR".to_string()),
+            dfs_region: Some(Nl80211DfsRegions::DfsEtsi),
+            self_managed: true,
+            rules: Some(vec![RegRule {
+                flags: Some(vec![
+                    Nl80211RegRuleFlags::RrfDfs,
+                    Nl80211RegRuleFlags::RrfNo160mhz,
+                ]),
+                start_freq: Some(5_490_000),
+                end_freq: Some(5_710_000),
+                max_bandwidth: Some(160_000),
+                ..Default::default()
+            }]),
+        };
+
+        assert_eq!(domain, expected_domain)
+    }
+}
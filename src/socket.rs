@@ -1,17 +1,43 @@
-use crate::attr::Nl80211Attr;
-use crate::bss::Bss;
+use crate::ap::{ApSettings, BeaconData, ChannelSwitchParams, MacAcl, ProbeStatus};
+use crate::attr::{
+    new_attr, new_nested_attr, Nl80211Attr, Nl80211Iftype, Nl80211KeyAttributes,
+    Nl80211KeyDefaultTypes, Nl80211MntrFlags, Nl80211PsState, Nl80211TxPowerSetting,
+};
+use crate::bitrate::BitrateMask;
+use crate::bss::{Bss, BssParams};
+use crate::channel::ChannelDef;
 use crate::cmd::Nl80211Cmd;
-use crate::interface::Interface;
+use crate::connect::ConnectParams;
+use crate::event::Event;
+use crate::interface::{
+    index_from_name, Interface, InterfaceId, InterfaceOptions, SetInterfaceError,
+};
+use crate::key::{Key, KeyParams, RekeyParams};
+use crate::mesh::{JoinMeshParams, MeshConfig};
+use crate::mlme::{AssociateParams, AuthenticateParams, MlmeResult};
+use crate::mpath::MeshPath;
+use crate::pmksa::PmksaParams;
+use crate::reg::RegDomain;
 use crate::station::Station;
+use crate::wiphy::{WiphyId, WiphyParams};
 use crate::{Attrs, NL_80211_GENL_NAME, NL_80211_GENL_VERSION};
 
+use std::io::{self, Cursor};
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::time::{Duration, Instant};
+
+use neli::attr::Attribute;
 use neli::consts::genl::{CtrlAttr, CtrlCmd};
-use neli::consts::{nl::GenlId, nl::NlmF, nl::NlmFFlags, nl::Nlmsg, socket::NlFamily};
-use neli::err::{DeError, NlError};
-use neli::genl::{Genlmsghdr, Nlattr};
+use neli::consts::{nl::GenlId, nl::NlType, nl::NlmF, nl::NlmFFlags, nl::Nlmsg, socket::NlFamily};
+use neli::err::{DeError, NlError, Nlmsgerr, NlmsghdrErr};
+use neli::genl::Genlmsghdr;
 use neli::nl::{NlPayload, Nlmsghdr};
 use neli::socket::NlSocketHandle;
-use neli::types::GenlBuffer;
+use neli::types::{Buffer, GenlBuffer};
+use neli::ToBytes;
+
+/// How long to wait for the notification reporting the outcome of a command
+pub(crate) const EVENT_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// A generic netlink socket to send commands and receive messages
 pub struct Socket {
@@ -19,6 +45,107 @@ pub struct Socket {
     pub(crate) family_id: u16,
 }
 
+/// Build a request message for the nl80211 family
+pub(crate) fn nl80211_request(
+    family_id: u16,
+    cmd: Nl80211Cmd,
+    attrs: GenlBuffer<Nl80211Attr, Buffer>,
+    dump: bool,
+) -> Nlmsghdr<u16, Genlmsghdr<Nl80211Cmd, Nl80211Attr>> {
+    let msghdr = Genlmsghdr::<Nl80211Cmd, Nl80211Attr>::new(cmd, NL_80211_GENL_VERSION, attrs);
+
+    let len = None;
+    let nl_type = family_id;
+    let flags = if dump {
+        NlmFFlags::new(&[NlmF::Request, NlmF::Dump])
+    } else {
+        NlmFFlags::new(&[NlmF::Request, NlmF::Ack])
+    };
+    let seq = None;
+    let pid = None;
+    let payload = NlPayload::Payload(msghdr);
+    Nlmsghdr::new(len, nl_type, flags, seq, pid, payload)
+}
+
+/// Convert an error reported by the kernel into an untyped [`NlError`], keeping the error code
+pub(crate) fn convert_nlmsgerr<T, P>(err: Nlmsgerr<T, P>) -> NlError
+where
+    T: NlType,
+    P: ToBytes,
+{
+    let mut payload = Cursor::new(Vec::new());
+    if let Err(e) = err.nlmsg.nl_payload.to_bytes(&mut payload) {
+        return NlError::Ser(e);
+    }
+    NlError::Nlmsgerr(Nlmsgerr {
+        error: err.error,
+        nlmsg: NlmsghdrErr {
+            nl_len: err.nlmsg.nl_len,
+            nl_type: err.nlmsg.nl_type.into(),
+            nl_flags: err.nlmsg.nl_flags,
+            nl_seq: err.nlmsg.nl_seq,
+            nl_pid: err.nlmsg.nl_pid,
+            nl_payload: Buffer::from(payload.into_inner()),
+        },
+    })
+}
+
+fn convert_err<T, P>(err: NlError<T, P>) -> NlError
+where
+    T: NlType,
+    P: ToBytes,
+{
+    match err {
+        NlError::Msg(msg) => NlError::Msg(msg),
+        NlError::Nlmsgerr(err) => convert_nlmsgerr(err),
+        NlError::Ser(err) => NlError::Ser(err),
+        NlError::De(err) => NlError::De(err),
+        NlError::Wrapped(err) => NlError::Wrapped(err),
+        NlError::NoAck => NlError::NoAck,
+        NlError::BadSeq => NlError::BadSeq,
+        NlError::BadPid => NlError::BadPid,
+    }
+}
+
+/// Check whether a notification is the answer to `cmd` sent on the given interface
+pub(crate) fn is_reply_to(
+    msg: &Genlmsghdr<Nl80211Cmd, Nl80211Attr>,
+    cmd: Nl80211Cmd,
+    interface_index: i32,
+) -> bool {
+    msg.cmd == cmd
+        && msg
+            .get_attr_handle()
+            .get_attr_payload_as::<i32>(Nl80211Attr::AttrIfindex)
+            .is_ok_and(|index| index == interface_index)
+}
+
+/// Make the blocking receive calls on `fd` give up after `timeout`
+fn set_recv_timeout(fd: RawFd, timeout: Duration) -> Result<(), NlError> {
+    let timeval = libc::timeval {
+        tv_sec: timeout.as_secs() as libc::time_t,
+        // A zero timeout would disable it
+        tv_usec: match timeout.as_secs() {
+            0 => timeout.subsec_micros().max(1),
+            _ => timeout.subsec_micros(),
+        } as libc::suseconds_t,
+    };
+    // SAFETY: `timeval` is a valid `struct timeval` and its size is given
+    let res = unsafe {
+        libc::setsockopt(
+            fd,
+            libc::SOL_SOCKET,
+            libc::SO_RCVTIMEO,
+            &timeval as *const libc::timeval as *const libc::c_void,
+            std::mem::size_of::<libc::timeval>() as libc::socklen_t,
+        )
+    };
+    match res {
+        0 => Ok(()),
+        _ => Err(io::Error::last_os_error().into()),
+    }
+}
+
 impl Socket {
     /// Create a new nl80211 socket with netlink
     pub fn connect() -> Result<Self, NlError<GenlId, Genlmsghdr<CtrlCmd, CtrlAttr>>> {
@@ -27,35 +154,70 @@ impl Socket {
         Ok(Self { sock, family_id })
     }
 
-    fn get_info_vec<T>(
-        &mut self,
-        interface_index: Option<i32>,
-        cmd: Nl80211Cmd,
-    ) -> Result<Vec<T>, NlError>
-    where
-        T: for<'a> TryFrom<Attrs<'a, Nl80211Attr>, Error = DeError>,
-    {
-        let msghdr = Genlmsghdr::<Nl80211Cmd, Nl80211Attr>::new(cmd, NL_80211_GENL_VERSION, {
-            let mut attrs = GenlBuffer::new();
-            if let Some(interface_index) = interface_index {
-                attrs.push(
-                    Nlattr::new(false, false, Nl80211Attr::AttrIfindex, interface_index).unwrap(),
-                );
+    /// Create a new nl80211 socket subscribed to the multicast group `group`
+    pub(crate) fn subscribe(group: &str) -> Result<Self, NlError> {
+        let mut socket = Self::connect().map_err(convert_err)?;
+        let id = socket
+            .sock
+            .resolve_nl_mcast_group(NL_80211_GENL_NAME, group)
+            .map_err(convert_err)?;
+        socket.sock.add_mcast_membership(&[id])?;
+        Ok(socket)
+    }
+
+    /// Create a new nl80211 socket receiving the notifications of the "mlme" multicast group
+    ///
+    /// The notifications are read with [`Socket::next_event`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{Event, Socket};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// let mut events = Socket::connect_mlme_events()?;
+    /// loop {
+    ///     if let Event::Disconnect { interface_index, .. } = events.next_event()? {
+    ///         println!("interface {} disconnected", interface_index);
+    ///     }
+    /// }
+    /// # }
+    ///```
+    pub fn connect_mlme_events() -> Result<Self, NlError> {
+        Self::subscribe("mlme")
+    }
+
+    /// Wait for the next notification
+    ///
+    /// Notifications that cannot be decoded or represented as an [`Event`] are skipped.
+    pub fn next_event(&mut self) -> Result<Event, NlError> {
+        let iter = self
+            .sock
+            .iter::<u16, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>(true);
+
+        for response in iter {
+            if let NlPayload::Payload(msg) = response.map_err(convert_err)?.nl_payload {
+                if let Some(event) = Event::from_msg(&msg) {
+                    return Ok(event);
+                }
             }
-            attrs
-        });
-
-        let nlhdr = {
-            let len = None;
-            let nl_type = self.family_id;
-            let flags = NlmFFlags::new(&[NlmF::Request, NlmF::Dump]);
-            let seq = None;
-            let pid = None;
-            let payload = NlPayload::Payload(msghdr);
-            Nlmsghdr::new(len, nl_type, flags, seq, pid, payload)
-        };
+        }
 
-        self.sock.send(nlhdr)?;
+        Err(NlError::msg("No event received"))
+    }
+
+    /// Send a command and collect the messages sent back by the kernel
+    ///
+    /// Non-dump requests ask for an acknowledgement, so errors reported by the kernel are
+    /// returned as [`NlError::Nlmsgerr`].
+    fn request(
+        &mut self,
+        cmd: Nl80211Cmd,
+        attrs: GenlBuffer<Nl80211Attr, Buffer>,
+        dump: bool,
+    ) -> Result<Vec<Genlmsghdr<Nl80211Cmd, Nl80211Attr>>, NlError> {
+        self.sock
+            .send(nl80211_request(self.family_id, cmd, attrs, dump))?;
 
         let iter = self
             .sock
@@ -64,25 +226,95 @@ impl Socket {
         let mut retval = Vec::new();
 
         for response in iter {
-            let response = response.unwrap();
+            let response = response.map_err(convert_err)?;
             match response.nl_type {
                 Nlmsg::Noop => (),
-                Nlmsg::Error => panic!("Error"),
-                Nlmsg::Done => break,
-                _ => retval.push(
-                    response
-                        .nl_payload
-                        .get_payload()
-                        .unwrap()
-                        .get_attr_handle()
-                        .try_into()?,
-                ),
+                Nlmsg::Error | Nlmsg::Done => break,
+                _ => {
+                    if let NlPayload::Payload(payload) = response.nl_payload {
+                        retval.push(payload);
+                    }
+                }
             }
         }
 
         Ok(retval)
     }
 
+    fn get_info_vec<T>(
+        &mut self,
+        cmd: Nl80211Cmd,
+        attrs: GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<Vec<T>, NlError>
+    where
+        T: for<'a> TryFrom<Attrs<'a, Nl80211Attr>, Error = DeError>,
+    {
+        let mut retval = Vec::new();
+        for msg in self.request(cmd, attrs, true)? {
+            retval.push(msg.get_attr_handle().try_into()?);
+        }
+        Ok(retval)
+    }
+
+    fn get_info<T>(
+        &mut self,
+        cmd: Nl80211Cmd,
+        attrs: GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<T, NlError>
+    where
+        T: for<'a> TryFrom<Attrs<'a, Nl80211Attr>, Error = DeError>,
+    {
+        match self.request(cmd, attrs, false)?.first() {
+            Some(msg) => Ok(msg.get_attr_handle().try_into()?),
+            None => Err(NlError::msg("No response received")),
+        }
+    }
+
+    fn send_cmd(
+        &mut self,
+        cmd: Nl80211Cmd,
+        attrs: GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), NlError> {
+        self.request(cmd, attrs, false)?;
+        Ok(())
+    }
+
+    /// Send an MLME command and wait for the notification carrying its result
+    ///
+    /// Gives up with [`MlmeResult::TimedOut`] if it doesn't arrive within [`EVENT_TIMEOUT`].
+    fn mlme_request(
+        &mut self,
+        cmd: Nl80211Cmd,
+        interface_index: i32,
+        attrs: GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<MlmeResult, NlError> {
+        // Subscribe before sending the command so that the result cannot be missed
+        let mut events = Self::subscribe("mlme")?;
+        self.send_cmd(cmd, attrs)?;
+
+        let deadline = Instant::now() + EVENT_TIMEOUT;
+        let fd = events.sock.as_raw_fd();
+        set_recv_timeout(fd, EVENT_TIMEOUT)?;
+        let iter = events
+            .sock
+            .iter::<u16, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>(true);
+
+        // The iterator ends when the receive call times out
+        for response in iter {
+            if let NlPayload::Payload(msg) = response.map_err(convert_err)?.nl_payload {
+                if is_reply_to(&msg, cmd, interface_index) {
+                    return Ok(msg.get_attr_handle().try_into()?);
+                }
+            }
+            match deadline.checked_duration_since(Instant::now()) {
+                Some(remaining) => set_recv_timeout(fd, remaining)?,
+                None => break,
+            }
+        }
+
+        Ok(MlmeResult::TimedOut)
+    }
+
     /// Get information for all your wifi interfaces
     ///
     /// # Example
@@ -99,7 +331,38 @@ impl Socket {
     /// # }
     ///```
     pub fn get_interfaces_info(&mut self) -> Result<Vec<Interface>, NlError> {
-        self.get_info_vec(None, Nl80211Cmd::CmdGetInterface)
+        self.get_info_vec(Nl80211Cmd::CmdGetInterface, GenlBuffer::new())
+    }
+
+    /// Get information for a single wifi interface
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::Socket;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// let wifi_interface = Socket::connect()?.get_interface_by_name("wlan0")?;
+    /// println!("{:#?}", wifi_interface);
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn get_interface(&mut self, interface_index: i32) -> Result<Interface, NlError> {
+        let mut attrs = GenlBuffer::new();
+        InterfaceId::Index(interface_index).append_attrs(&mut attrs)?;
+        self.get_info(Nl80211Cmd::CmdGetInterface, attrs)
+    }
+
+    /// Get information for the wifi interface `name`
+    pub fn get_interface_by_name(&mut self, name: &str) -> Result<Interface, NlError> {
+        self.get_interface(index_from_name(name)?)
+    }
+
+    /// Get information for the wireless device `wdev`, which may not have a netdev
+    pub fn get_interface_by_wdev(&mut self, wdev: u64) -> Result<Interface, NlError> {
+        let mut attrs = GenlBuffer::new();
+        InterfaceId::Wdev(wdev).append_attrs(&mut attrs)?;
+        self.get_info(Nl80211Cmd::CmdGetInterface, attrs)
     }
 
     /// Get access point information for a specific interface
@@ -124,11 +387,938 @@ impl Socket {
     /// # }
     ///```
     pub fn get_station_info(&mut self, interface_index: i32) -> Result<Vec<Station>, NlError> {
-        self.get_info_vec(Some(interface_index), Nl80211Cmd::CmdGetStation)
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        self.get_info_vec(Nl80211Cmd::CmdGetStation, attrs)
     }
 
     pub fn get_bss_info(&mut self, interface_index: i32) -> Result<Vec<Bss>, NlError> {
-        self.get_info_vec(Some(interface_index), Nl80211Cmd::CmdGetScan)
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        self.get_info_vec(Nl80211Cmd::CmdGetScan, attrs)
+    }
+
+    /// Get the mesh paths known to a mesh point interface
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::Socket;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// let mut socket = Socket::connect()?;
+    /// for mesh_path in socket.get_mesh_paths(3)? {
+    ///     println!("{:?} via {:?}", mesh_path.destination, mesh_path.next_hop);
+    /// }
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn get_mesh_paths(&mut self, interface_index: i32) -> Result<Vec<MeshPath>, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        self.get_info_vec(Nl80211Cmd::CmdGetMpath, attrs)
+    }
+
+    /// Get the mesh proxy paths of a mesh point interface
+    ///
+    /// For proxy paths, [`MeshPath::next_hop`] is the mesh proxy (gate) the destination is
+    /// reachable through.
+    pub fn get_mesh_proxy_paths(&mut self, interface_index: i32) -> Result<Vec<MeshPath>, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        self.get_info_vec(Nl80211Cmd::CmdGetMpp, attrs)
+    }
+
+    /// Add a static mesh path to `destination` through `next_hop`
+    pub fn new_mesh_path(
+        &mut self,
+        interface_index: i32,
+        destination: &[u8],
+        next_hop: &[u8],
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrMac, destination)?);
+        attrs.push(new_attr(Nl80211Attr::AttrMpathNextHop, next_hop)?);
+        self.send_cmd(Nl80211Cmd::CmdNewMpath, attrs)
+    }
+
+    /// Change the next hop of an existing mesh path
+    pub fn set_mesh_path(
+        &mut self,
+        interface_index: i32,
+        destination: &[u8],
+        next_hop: &[u8],
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrMac, destination)?);
+        attrs.push(new_attr(Nl80211Attr::AttrMpathNextHop, next_hop)?);
+        self.send_cmd(Nl80211Cmd::CmdSetMpath, attrs)
+    }
+
+    /// Delete the mesh path to `destination`
+    pub fn del_mesh_path(
+        &mut self,
+        interface_index: i32,
+        destination: &[u8],
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrMac, destination)?);
+        self.send_cmd(Nl80211Cmd::CmdDelMpath, attrs)
+    }
+
+    /// Join an 802.11s mesh
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{ChannelDef, JoinMeshParams, Socket};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// let params = JoinMeshParams::new(b"backhaul")
+    ///     .channel(ChannelDef::new(2412))
+    ///     .forwarding(true);
+    /// Socket::connect()?.join_mesh(3, &params)?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn join_mesh(
+        &mut self,
+        interface_index: i32,
+        params: &JoinMeshParams,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        params.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdJoinMesh, attrs)
+    }
+
+    /// Leave the mesh the interface is part of
+    pub fn leave_mesh(&mut self, interface_index: i32) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        self.send_cmd(Nl80211Cmd::CmdLeaveMesh, attrs)
+    }
+
+    /// Get the configuration of a mesh point interface
+    pub fn get_mesh_config(&mut self, interface_index: i32) -> Result<MeshConfig, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        self.get_info(Nl80211Cmd::CmdGetMeshConfig, attrs)
+    }
+
+    /// Change the configuration of a mesh point interface
+    ///
+    /// Only the fields of `config` that are set are changed.
+    pub fn set_mesh_config(
+        &mut self,
+        interface_index: i32,
+        config: &MeshConfig,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_nested_attr(
+            Nl80211Attr::AttrMeshConfig,
+            &config.attrs()?,
+        )?);
+        self.send_cmd(Nl80211Cmd::CmdSetMeshConfig, attrs)
+    }
+
+    /// Connect to a network using the SME implemented by the driver
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{ConnectParams, Socket};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// Socket::connect()?.connect_network(3, &ConnectParams::new(b"guest"))?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn connect_network(
+        &mut self,
+        interface_index: i32,
+        params: &ConnectParams,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        params.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdConnect, attrs)
+    }
+
+    /// Disconnect from the current network with the given reason code
+    pub fn disconnect(&mut self, interface_index: i32, reason: u16) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrReasonCode, reason)?);
+        self.send_cmd(Nl80211Cmd::CmdDisconnect, attrs)
+    }
+
+    /// Authenticate with a BSS and wait for its answer
+    ///
+    /// Waits for up to 5 seconds, [`MlmeResult::TimedOut`] being returned if no answer is
+    /// reported in time.
+    ///
+    /// This is used by userspace SMEs, for drivers without one.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{AuthenticateParams, MlmeResult, Nl80211AuthType, Socket};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// let bssid = [0x02, 0, 0, 0, 1, 0];
+    /// let params =
+    ///     AuthenticateParams::new(&bssid, 2412, b"home", Nl80211AuthType::AuthtypeOpenSystem);
+    /// match Socket::connect()?.authenticate(3, &params)? {
+    ///     MlmeResult::Response(frame) => println!("authenticated: {:?}", frame),
+    ///     _ => println!("no answer"),
+    /// }
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn authenticate(
+        &mut self,
+        interface_index: i32,
+        params: &AuthenticateParams,
+    ) -> Result<MlmeResult, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        params.append_attrs(&mut attrs)?;
+        self.mlme_request(Nl80211Cmd::CmdAuthenticate, interface_index, attrs)
+    }
+
+    /// Associate with a BSS we are authenticated with and wait for its answer
+    pub fn associate(
+        &mut self,
+        interface_index: i32,
+        params: &AssociateParams,
+    ) -> Result<MlmeResult, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        params.append_attrs(&mut attrs)?;
+        self.mlme_request(Nl80211Cmd::CmdAssociate, interface_index, attrs)
+    }
+
+    /// Deauthenticate from a BSS with the given reason code
+    pub fn deauthenticate(
+        &mut self,
+        interface_index: i32,
+        bssid: &[u8],
+        reason: u16,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrMac, bssid)?);
+        attrs.push(new_attr(Nl80211Attr::AttrReasonCode, reason)?);
+        self.send_cmd(Nl80211Cmd::CmdDeauthenticate, attrs)
+    }
+
+    /// Disassociate from a BSS with the given reason code
+    pub fn disassociate(
+        &mut self,
+        interface_index: i32,
+        bssid: &[u8],
+        reason: u16,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrMac, bssid)?);
+        attrs.push(new_attr(Nl80211Attr::AttrReasonCode, reason)?);
+        self.send_cmd(Nl80211Cmd::CmdDisassociate, attrs)
+    }
+
+    /// Install a key
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{KeyParams, Nl80211KeyDefaultTypes, Socket};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// let mut socket = Socket::connect()?;
+    /// socket.new_key(3, &KeyParams::new(1, 0x000fac04, &[0; 16]))?;
+    /// socket.set_default_key(3, 1, &[Nl80211KeyDefaultTypes::KeyDefaultTypeMulticast])?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn new_key(&mut self, interface_index: i32, key: &KeyParams) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        key.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdNewKey, attrs)
+    }
+
+    /// Get the key stored at `key_index`, the pairwise key of the peer `mac` if given
+    ///
+    /// This is mostly useful to read the current TX sequence counter of a key.
+    pub fn get_key(
+        &mut self,
+        interface_index: i32,
+        key_index: u8,
+        mac: Option<&[u8]>,
+    ) -> Result<Key, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrKeyIdx, key_index)?);
+        if let Some(mac) = mac {
+            attrs.push(new_attr(Nl80211Attr::AttrMac, mac)?);
+        }
+        self.get_info(Nl80211Cmd::CmdGetKey, attrs)
+    }
+
+    /// Delete the key stored at `key_index`, the pairwise key of the peer `mac` if given
+    pub fn del_key(
+        &mut self,
+        interface_index: i32,
+        key_index: u8,
+        mac: Option<&[u8]>,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrKeyIdx, key_index)?);
+        if let Some(mac) = mac {
+            attrs.push(new_attr(Nl80211Attr::AttrMac, mac)?);
+        }
+        self.send_cmd(Nl80211Cmd::CmdDelKey, attrs)
+    }
+
+    /// Use the key stored at `key_index` as default key to transmit data frames
+    ///
+    /// The key is used for both unicast and multicast frames if `types` is empty.
+    pub fn set_default_key(
+        &mut self,
+        interface_index: i32,
+        key_index: u8,
+        types: &[Nl80211KeyDefaultTypes],
+    ) -> Result<(), NlError> {
+        let mut key = vec![
+            new_attr(Nl80211KeyAttributes::KeyIdx, key_index)?,
+            new_attr(Nl80211KeyAttributes::KeyDefault, ())?,
+        ];
+        if !types.is_empty() {
+            let types = types
+                .iter()
+                .map(|t| new_attr(*t, ()))
+                .collect::<Result<Vec<_>, _>>()?;
+            key.push(new_nested_attr(
+                Nl80211KeyAttributes::KeyDefaultTypes,
+                &types,
+            )?);
+        }
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_nested_attr(Nl80211Attr::AttrKey, &key)?);
+        self.send_cmd(Nl80211Cmd::CmdSetKey, attrs)
+    }
+
+    /// Use the IGTK stored at `key_index` to protect transmitted management frames
+    pub fn set_default_mgmt_key(
+        &mut self,
+        interface_index: i32,
+        key_index: u8,
+    ) -> Result<(), NlError> {
+        let key = [
+            new_attr(Nl80211KeyAttributes::KeyIdx, key_index)?,
+            new_attr(Nl80211KeyAttributes::KeyDefaultMgmt, ())?,
+        ];
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_nested_attr(Nl80211Attr::AttrKey, &key)?);
+        self.send_cmd(Nl80211Cmd::CmdSetKey, attrs)
+    }
+
+    /// Use the BIGTK stored at `key_index` to protect transmitted beacons
+    pub fn set_default_beacon_key(
+        &mut self,
+        interface_index: i32,
+        key_index: u8,
+    ) -> Result<(), NlError> {
+        let key = [
+            new_attr(Nl80211KeyAttributes::KeyIdx, key_index)?,
+            new_attr(Nl80211KeyAttributes::KeyDefaultBeacon, ())?,
+        ];
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_nested_attr(Nl80211Attr::AttrKey, &key)?);
+        self.send_cmd(Nl80211Cmd::CmdSetKey, attrs)
+    }
+
+    /// Add a PMKSA cache entry to the driver
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{PmksaParams, Socket};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// let entry = PmksaParams::new(&[0; 16]).bssid(&[0x02, 0, 0, 0, 1, 0]);
+    /// Socket::connect()?.set_pmksa(3, &entry)?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn set_pmksa(&mut self, interface_index: i32, entry: &PmksaParams) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        entry.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdSetPmksa, attrs)
+    }
+
+    /// Delete a PMKSA cache entry from the driver
+    pub fn del_pmksa(&mut self, interface_index: i32, entry: &PmksaParams) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        entry.append_id_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdDelPmksa, attrs)
+    }
+
+    /// Delete all the PMKSA cache entries of the driver
+    pub fn flush_pmksa(&mut self, interface_index: i32) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        self.send_cmd(Nl80211Cmd::CmdFlushPmksa, attrs)
+    }
+
+    /// Give the device the keys it needs to handle group rekeys while the host is asleep
+    ///
+    /// The device reports completed rekeys with [`Event::GtkRekey`](crate::Event::GtkRekey).
+    pub fn set_rekey_offload(
+        &mut self,
+        interface_index: i32,
+        rekey: &RekeyParams,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        rekey.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdSetRekeyOffload, attrs)
+    }
+
+    /// Create a new interface on the wiphy `phy` and return it
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{InterfaceOptions, Nl80211Iftype, Socket};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// let options = InterfaceOptions::new();
+    /// let interface = Socket::connect()?
+    ///     .create_interface(0, "mon0", Nl80211Iftype::IftypeMonitor, &options)?;
+    /// println!("{:?}", interface.index);
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn create_interface(
+        &mut self,
+        phy: u32,
+        name: &str,
+        iftype: Nl80211Iftype,
+        options: &InterfaceOptions,
+    ) -> Result<Interface, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrWiphy, phy)?);
+        attrs.push(new_attr(Nl80211Attr::AttrIfname, name)?);
+        attrs.push(new_attr(
+            Nl80211Attr::AttrIftype,
+            u32::from(u16::from(iftype)),
+        )?);
+        options.append_attrs(&mut attrs)?;
+        self.get_info(Nl80211Cmd::CmdNewInterface, attrs)
+    }
+
+    /// Delete an interface
+    pub fn delete_interface(&mut self, id: InterfaceId) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        id.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdDelInterface, attrs)
+    }
+
+    /// Change the type of an interface
+    ///
+    /// Most drivers require the interface to be down, [`SetInterfaceError::Busy`] is returned
+    /// otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{Nl80211Iftype, SetInterfaceError, Socket};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// let mut socket = Socket::connect()?;
+    /// match socket.set_interface_type(3, Nl80211Iftype::IftypeAp) {
+    ///     Err(SetInterfaceError::Busy) => println!("bring the interface down first"),
+    ///     res => res?,
+    /// }
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn set_interface_type(
+        &mut self,
+        interface_index: i32,
+        iftype: Nl80211Iftype,
+    ) -> Result<(), SetInterfaceError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(
+            Nl80211Attr::AttrIftype,
+            u32::from(u16::from(iftype)),
+        )?);
+        Ok(self.send_cmd(Nl80211Cmd::CmdSetInterface, attrs)?)
+    }
+
+    /// Enable or disable 4-address frames on an interface
+    pub fn set_four_addr(
+        &mut self,
+        interface_index: i32,
+        four_addr: bool,
+    ) -> Result<(), SetInterfaceError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        InterfaceOptions::new()
+            .four_addr(four_addr)
+            .append_attrs(&mut attrs)?;
+        Ok(self.send_cmd(Nl80211Cmd::CmdSetInterface, attrs)?)
+    }
+
+    /// Set the flags of a monitor interface
+    pub fn set_monitor_flags(
+        &mut self,
+        interface_index: i32,
+        flags: &[Nl80211MntrFlags],
+    ) -> Result<(), SetInterfaceError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        InterfaceOptions::new()
+            .monitor_flags(flags)
+            .append_attrs(&mut attrs)?;
+        Ok(self.send_cmd(Nl80211Cmd::CmdSetInterface, attrs)?)
+    }
+
+    /// Set the mesh ID of a mesh point interface
+    pub fn set_interface_mesh_id(
+        &mut self,
+        interface_index: i32,
+        mesh_id: &[u8],
+    ) -> Result<(), SetInterfaceError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrMeshId, mesh_id)?);
+        Ok(self.send_cmd(Nl80211Cmd::CmdSetInterface, attrs)?)
+    }
+
+    /// Set the operating channel of a monitor, AP or mesh interface
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{ChannelDef, Socket};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// let mut socket = Socket::connect()?;
+    /// for frequency in [2412, 2437, 2462] {
+    ///     socket.set_channel(3, &ChannelDef::new(frequency))?;
+    /// }
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn set_channel(
+        &mut self,
+        interface_index: i32,
+        channel: &ChannelDef,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        channel.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdSetChannel, attrs)
+    }
+
+    /// Change the settings of a wiphy
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{WiphyId, WiphyParams, Socket};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// let params = WiphyParams::new().frag_threshold(u32::MAX).coverage_class(2);
+    /// Socket::connect()?.set_wiphy(WiphyId::Phy(0), &params)?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn set_wiphy(&mut self, wiphy: WiphyId, params: &WiphyParams) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        wiphy.append_attrs(&mut attrs)?;
+        params.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdSetWiphy, attrs)
+    }
+
+    /// Set the transmit power of a wiphy, or of a single interface if supported by the driver
+    ///
+    /// `mbm` is the fixed level or the limit in mBm, it is ignored with
+    /// [`Nl80211TxPowerSetting::TxPowerAutomatic`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{Nl80211TxPowerSetting, WiphyId, Socket};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// let mut socket = Socket::connect()?;
+    /// for dbm in 5..=20 {
+    ///     let setting = Nl80211TxPowerSetting::TxPowerFixed;
+    ///     socket.set_tx_power(WiphyId::Interface(3), setting, dbm * 100)?;
+    /// }
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn set_tx_power(
+        &mut self,
+        wiphy: WiphyId,
+        setting: Nl80211TxPowerSetting,
+        mbm: i32,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        wiphy.append_attrs(&mut attrs)?;
+        attrs.push(new_attr(
+            Nl80211Attr::AttrWiphyTxPowerSetting,
+            u32::from(u16::from(setting)),
+        )?);
+        if setting != Nl80211TxPowerSetting::TxPowerAutomatic {
+            attrs.push(new_attr(Nl80211Attr::AttrWiphyTxPowerLevel, mbm)?);
+        }
+        self.send_cmd(Nl80211Cmd::CmdSetWiphy, attrs)
+    }
+
+    /// Get the power save state of an interface
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{Nl80211PsState, Socket};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// let mut socket = Socket::connect()?;
+    /// if socket.get_power_save(3)? == Nl80211PsState::PsEnabled {
+    ///     socket.set_power_save(3, Nl80211PsState::PsDisabled)?;
+    /// }
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn get_power_save(&mut self, interface_index: i32) -> Result<Nl80211PsState, NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        match self
+            .request(Nl80211Cmd::CmdGetPowerSave, attrs, false)?
+            .first()
+        {
+            Some(msg) => {
+                let state: u32 = msg
+                    .get_attr_handle()
+                    .get_attr_payload_as(Nl80211Attr::AttrPsState)?;
+                Ok(Nl80211PsState::from(state as u16))
+            }
+            None => Err(NlError::msg("No response received")),
+        }
+    }
+
+    /// Enable or disable power save on an interface
+    pub fn set_power_save(
+        &mut self,
+        interface_index: i32,
+        state: Nl80211PsState,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(
+            Nl80211Attr::AttrPsState,
+            u32::from(u16::from(state)),
+        )?);
+        self.send_cmd(Nl80211Cmd::CmdSetPowerSave, attrs)
+    }
+
+    /// Restrict the rates used to transmit on an interface
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{BandRates, BitrateMask, Nl80211Bandc, Socket};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// let mask = BitrateMask::new().band(Nl80211Bandc::Band2ghz, BandRates::new().ht_mcs(&[7]));
+    /// Socket::connect()?.set_tx_bitrate_mask(3, &mask)?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn set_tx_bitrate_mask(
+        &mut self,
+        interface_index: i32,
+        mask: &BitrateMask,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        mask.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdSetTxBitrateMask, attrs)
+    }
+
+    /// Set the rate used to transmit multicast frames, in units of 100 kbps
+    pub fn set_mcast_rate(&mut self, interface_index: i32, rate: u32) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrMcastRate, rate)?);
+        self.send_cmd(Nl80211Cmd::CmdSetMcastRate, attrs)
+    }
+
+    /// Change the parameters of the BSS of an AP or mesh interface
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{BssParams, Socket};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// let params = BssParams::new().cts_prot(true).basic_rates(&[12, 24, 48]);
+    /// Socket::connect()?.set_bss(3, &params)?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn set_bss(&mut self, interface_index: i32, params: &BssParams) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        params.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdSetBss, attrs)
+    }
+
+    /// Start operating as an AP
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{ApSettings, BeaconData, Socket};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// # let (head, tail) = (vec![], vec![]);
+    /// let settings = ApSettings::new(b"portal", BeaconData::new(&head, &tail));
+    /// Socket::connect()?.start_ap(3, &settings)?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn start_ap(&mut self, interface_index: i32, settings: &ApSettings) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        settings.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdStartAp, attrs)
+    }
+
+    /// Stop operating as an AP
+    pub fn stop_ap(&mut self, interface_index: i32) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        self.send_cmd(Nl80211Cmd::CmdStopAp, attrs)
+    }
+
+    /// Update the beacon and probe response templates of a running AP
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{BeaconBuilder, Socket};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// let beacon = BeaconBuilder::new(&[0x02, 0, 0, 0, 1, 0], b"portal").ds_channel(6).build();
+    /// Socket::connect()?.set_beacon(3, &beacon)?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn set_beacon(&mut self, interface_index: i32, beacon: &BeaconData) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        beacon.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdSetBeacon, attrs)
+    }
+
+    /// Announce a channel switch and move the interface to the new channel
+    ///
+    /// AP and P2P GO interfaces need a CSA beacon, cf. [`ChannelSwitchParams`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{BeaconBuilder, ChannelDef, ChannelSwitchParams, Socket};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// let bssid = [0x02, 0, 0, 0, 1, 0];
+    /// let after = BeaconBuilder::new(&bssid, b"portal").build();
+    /// // Channel switch announcement element: mode, new channel and counter
+    /// let during = BeaconBuilder::new(&bssid, b"portal").tail_ies(&[0x25, 3, 1, 36, 5]).build();
+    /// let params = ChannelSwitchParams::new(ChannelDef::new(5180), 5)
+    ///     .beacon_after(after)
+    ///     .csa_beacon(during, &[4], &[]);
+    /// Socket::connect()?.channel_switch(3, &params)?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn channel_switch(
+        &mut self,
+        interface_index: i32,
+        params: &ChannelSwitchParams,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        params.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdChannelSwitch, attrs)
+    }
+
+    /// Start a channel availability check (CAC) on a DFS channel
+    ///
+    /// The duration of the check is set by the regulatory domain. The outcome is reported by
+    /// [`Event::Radar`](crate::Event::Radar) events.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{ChannelDef, Nl80211ChanWidth, Socket};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// let channel = ChannelDef::new(5500).with_width(Nl80211ChanWidth::ChanWidth80, 5530);
+    /// Socket::connect()?.start_radar_detection(3, &channel)?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn start_radar_detection(
+        &mut self,
+        interface_index: i32,
+        channel: &ChannelDef,
+    ) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        channel.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdRadarDetect, attrs)
+    }
+
+    /// Replace the MAC ACL of a running AP
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::{MacAcl, Nl80211AclPolicy, Socket};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// let acl = MacAcl::new(Nl80211AclPolicy::AclPolicyAcceptUnlessListed)
+    ///     .mac(&[0x02, 0, 0, 0, 1, 0]);
+    /// Socket::connect()?.set_mac_acl(3, &acl)?;
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn set_mac_acl(&mut self, interface_index: i32, acl: &MacAcl) -> Result<(), NlError> {
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        acl.append_attrs(&mut attrs)?;
+        self.send_cmd(Nl80211Cmd::CmdSetMacAcl, attrs)
+    }
+
+    /// Get the maximum number of MAC addresses in an ACL, `None` if ACLs aren't supported
+    pub fn get_mac_acl_max(&mut self, wiphy: WiphyId) -> Result<Option<u32>, NlError> {
+        let mut attrs = GenlBuffer::new();
+        wiphy.append_attrs(&mut attrs)?;
+        match self.request(Nl80211Cmd::CmdGetWiphy, attrs, false)?.first() {
+            Some(msg) => match msg
+                .get_attr_handle()
+                .get_attribute(Nl80211Attr::AttrMacAclMax)
+            {
+                Some(attr) => Ok(Some(attr.get_payload_as()?)),
+                None => Ok(None),
+            },
+            None => Err(NlError::msg("No response received")),
+        }
+    }
+
+    /// Send a null data frame to a station associated to an AP and wait for its TX status
+    ///
+    /// Waits for up to 5 seconds, an error being returned if no TX status is reported in time.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::Socket;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// let status = Socket::connect()?.probe_client(3, &[0x02, 0, 0, 0, 1, 0])?;
+    /// println!("station present: {}", status.acked);
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn probe_client(
+        &mut self,
+        interface_index: i32,
+        mac: &[u8],
+    ) -> Result<ProbeStatus, NlError> {
+        let cmd = Nl80211Cmd::CmdProbeClient;
+        let mut attrs = GenlBuffer::new();
+        attrs.push(new_attr(Nl80211Attr::AttrIfindex, interface_index)?);
+        attrs.push(new_attr(Nl80211Attr::AttrMac, mac)?);
+
+        // Subscribe before sending the command so that the status cannot be missed
+        let mut events = Self::subscribe("mlme")?;
+        let cookie: u64 = match self.request(cmd, attrs, false)?.first() {
+            Some(msg) => msg
+                .get_attr_handle()
+                .get_attr_payload_as(Nl80211Attr::AttrCookie)?,
+            None => return Err(NlError::msg("No response received")),
+        };
+
+        let deadline = Instant::now() + EVENT_TIMEOUT;
+        let fd = events.sock.as_raw_fd();
+        set_recv_timeout(fd, EVENT_TIMEOUT)?;
+        let iter = events
+            .sock
+            .iter::<u16, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>(true);
+
+        // The iterator ends when the receive call times out
+        for response in iter {
+            if let NlPayload::Payload(msg) = response.map_err(convert_err)?.nl_payload {
+                if is_reply_to(&msg, cmd, interface_index) {
+                    let status: ProbeStatus = msg.get_attr_handle().try_into()?;
+                    if status.cookie == Some(cookie) {
+                        return Ok(status);
+                    }
+                }
+            }
+            match deadline.checked_duration_since(Instant::now()) {
+                Some(remaining) => set_recv_timeout(fd, remaining)?,
+                None => break,
+            }
+        }
+
+        Err(NlError::msg("No TX status received"))
+    }
+
+    /// Get the regulatory domain of a wiphy, or the global one if `wiphy` is `None`
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use neli_wifi::Socket;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>>{
+    /// let domain = Socket::connect()?.get_regulatory(None)?;
+    /// println!("{:?}", domain.alpha2);
+    /// #   Ok(())
+    /// # }
+    ///```
+    pub fn get_regulatory(&mut self, wiphy: Option<u32>) -> Result<RegDomain, NlError> {
+        let mut attrs = GenlBuffer::new();
+        if let Some(wiphy) = wiphy {
+            attrs.push(new_attr(Nl80211Attr::AttrWiphy, wiphy)?);
+        }
+        self.get_info(Nl80211Cmd::CmdGetReg, attrs)
+    }
+
+    /// Get the global regulatory domain and the ones of the wiphys having their own
+    pub fn get_regulatory_domains(&mut self) -> Result<Vec<RegDomain>, NlError> {
+        self.get_info_vec(Nl80211Cmd::CmdGetReg, GenlBuffer::new())
     }
 }
 
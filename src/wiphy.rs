@@ -0,0 +1,223 @@
+use crate::attr::{new_attr, new_nested_attr, Nl80211Ac, Nl80211Attr, Nl80211TxqAttr};
+
+use neli::err::SerError;
+use neli::genl::Nlattr;
+use neli::types::{Buffer, GenlBuffer};
+
+/// Identifies a wiphy, directly or by one of its interfaces
+///
+/// Some settings, like TXQ parameters, need the wiphy to be identified by an interface and then
+/// only apply to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WiphyId {
+    /// Index of the wiphy, cf. /sys/class/ieee80211/<phyname>/index
+    Phy(u32),
+    /// Netlink index of an interface of the wiphy
+    Interface(i32),
+}
+
+impl WiphyId {
+    pub(crate) fn append_attrs(
+        &self,
+        attrs: &mut GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), SerError> {
+        match *self {
+            WiphyId::Phy(phy) => attrs.push(new_attr(Nl80211Attr::AttrWiphy, phy)?),
+            WiphyId::Interface(index) => attrs.push(new_attr(Nl80211Attr::AttrIfindex, index)?),
+        }
+        Ok(())
+    }
+}
+
+/// Parameters of the TX queue of an access category
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxqParams {
+    ac: Nl80211Ac,
+    txop: u16,
+    cwmin: u16,
+    cwmax: u16,
+    aifs: u8,
+}
+
+impl TxqParams {
+    /// TXOP limit in units of 32 µs, minimum and maximum contention windows and AIFSN of `ac`
+    pub fn new(ac: Nl80211Ac, txop: u16, cwmin: u16, cwmax: u16, aifs: u8) -> Self {
+        Self {
+            ac,
+            txop,
+            cwmin,
+            cwmax,
+            aifs,
+        }
+    }
+
+    fn attrs(&self) -> Result<Vec<Nlattr<Nl80211TxqAttr, Buffer>>, SerError> {
+        Ok(vec![
+            new_attr(Nl80211TxqAttr::TxqAttrAc, u16::from(self.ac) as u8)?,
+            new_attr(Nl80211TxqAttr::TxqAttrTxop, self.txop)?,
+            new_attr(Nl80211TxqAttr::TxqAttrCwmin, self.cwmin)?,
+            new_attr(Nl80211TxqAttr::TxqAttrCwmax, self.cwmax)?,
+            new_attr(Nl80211TxqAttr::TxqAttrAifs, self.aifs)?,
+        ])
+    }
+}
+
+/// Settings of a wiphy, only the ones that are set are changed
+///
+/// # Example
+///
+/// ```
+/// # use neli_wifi::WiphyParams;
+/// let params = WiphyParams::new()
+///     .rts_threshold(2347)
+///     .retry_limits(7, 4)
+///     .antenna(0b11, 0b11);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WiphyParams {
+    name: Option<String>,
+    rts_threshold: Option<u32>,
+    frag_threshold: Option<u32>,
+    retry_limits: Option<(u8, u8)>,
+    coverage_class: Option<u8>,
+    txq_params: Option<Vec<TxqParams>>,
+    antenna: Option<(u32, u32)>,
+}
+
+impl WiphyParams {
+    /// No changes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rename the wiphy
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// RTS threshold in bytes, `u32::MAX` disables RTS/CTS
+    pub fn rts_threshold(mut self, threshold: u32) -> Self {
+        self.rts_threshold = Some(threshold);
+        self
+    }
+
+    /// Fragmentation threshold in bytes, `u32::MAX` disables fragmentation
+    pub fn frag_threshold(mut self, threshold: u32) -> Self {
+        self.frag_threshold = Some(threshold);
+        self
+    }
+
+    /// Retry limits for frames shorter and longer than the RTS threshold
+    pub fn retry_limits(mut self, short: u8, long: u8) -> Self {
+        self.retry_limits = Some((short, long));
+        self
+    }
+
+    /// Coverage class, used to adjust the slot time for long distance links
+    pub fn coverage_class(mut self, coverage_class: u8) -> Self {
+        self.coverage_class = Some(coverage_class);
+        self
+    }
+
+    /// Parameters of the TX queues, the wiphy must be identified by an interface
+    pub fn txq_params(mut self, params: &[TxqParams]) -> Self {
+        self.txq_params = Some(params.to_vec());
+        self
+    }
+
+    /// Bitmaps of the antennas used to transmit and receive
+    pub fn antenna(mut self, tx: u32, rx: u32) -> Self {
+        self.antenna = Some((tx, rx));
+        self
+    }
+
+    pub(crate) fn append_attrs(
+        &self,
+        attrs: &mut GenlBuffer<Nl80211Attr, Buffer>,
+    ) -> Result<(), SerError> {
+        if let Some(name) = &self.name {
+            attrs.push(new_attr(Nl80211Attr::AttrWiphyName, name.as_str())?);
+        }
+        if let Some(threshold) = self.rts_threshold {
+            attrs.push(new_attr(Nl80211Attr::AttrWiphyRtsThreshold, threshold)?);
+        }
+        if let Some(threshold) = self.frag_threshold {
+            attrs.push(new_attr(Nl80211Attr::AttrWiphyFragThreshold, threshold)?);
+        }
+        if let Some((short, long)) = self.retry_limits {
+            attrs.push(new_attr(Nl80211Attr::AttrWiphyRetryShort, short)?);
+            attrs.push(new_attr(Nl80211Attr::AttrWiphyRetryLong, long)?);
+        }
+        if let Some(coverage_class) = self.coverage_class {
+            attrs.push(new_attr(
+                Nl80211Attr::AttrWiphyCoverageClass,
+                coverage_class,
+            )?);
+        }
+        if let Some(params) = &self.txq_params {
+            let queues = params
+                .iter()
+                .zip(1u16..)
+                .map(|(params, index)| new_nested_attr(index, &params.attrs()?))
+                .collect::<Result<Vec<_>, _>>()?;
+            attrs.push(new_nested_attr(Nl80211Attr::AttrWiphyTxqParams, &queues)?);
+        }
+        if let Some((tx, rx)) = self.antenna {
+            attrs.push(new_attr(Nl80211Attr::AttrWiphyAntennaTx, tx)?);
+            attrs.push(new_attr(Nl80211Attr::AttrWiphyAntennaRx, rx)?);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_wiphy_params {
+    use super::*;
+    use neli::ToBytes;
+    use std::io::Cursor;
+
+    fn to_bytes(attrs: GenlBuffer<Nl80211Attr, Buffer>) -> Vec<u8> {
+        let mut bytes = Cursor::new(Vec::new());
+        attrs.to_bytes(&mut bytes).unwrap();
+        bytes.into_inner()
+    }
+
+    #[test]
+    fn test_txq_params() {
+        let params = WiphyParams::new().rts_threshold(2347).txq_params(&[
+            TxqParams::new(Nl80211Ac::AcVo, 47, 3, 7, 2),
+            TxqParams::new(Nl80211Ac::AcBk, 0, 15, 1023, 7),
+        ]);
+        let mut attrs = GenlBuffer::new();
+        params.append_attrs(&mut attrs).unwrap();
+
+        assert_eq!(
+            to_bytes(attrs),
+            vec![
+                8, 0, 64, 0, 43, 9, 0, 0, // RTS threshold
+                92, 0, 37, 128, // TXQ parameters
+                44, 0, 1, 128, // first queue
+                5, 0, 1, 0, 0, 0, 0, 0, // AC_VO
+                6, 0, 2, 0, 47, 0, 0, 0, // TXOP
+                6, 0, 3, 0, 3, 0, 0, 0, // CWmin
+                6, 0, 4, 0, 7, 0, 0, 0, // CWmax
+                5, 0, 5, 0, 2, 0, 0, 0, // AIFSN
+                44, 0, 2, 128, // second queue
+                5, 0, 1, 0, 3, 0, 0, 0, // AC_BK
+                6, 0, 2, 0, 0, 0, 0, 0, // TXOP
+                6, 0, 3, 0, 15, 0, 0, 0, // CWmin
+                6, 0, 4, 0, 255, 3, 0, 0, // CWmax
+                5, 0, 5, 0, 7, 0, 0, 0, // AIFSN
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_changes() {
+        let mut attrs = GenlBuffer::new();
+        WiphyParams::new().append_attrs(&mut attrs).unwrap();
+
+        assert!(to_bytes(attrs).is_empty())
+    }
+}